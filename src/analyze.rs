@@ -1,20 +1,68 @@
 //! Analysis logic for WordPress scan results
 
-use crate::scanner::ScanResult;
-use serde::Serialize;
+use crate::error::{Error, Result};
+use crate::scanner::{Confidence, Evidence, PluginInfo, RestApiStatus, ScanResult};
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use time::{Date, Duration, Month, OffsetDateTime};
 
 /// Placeholder for unknown/missing version information
 const UNKNOWN_VERSION: &str = "-";
 
+/// Days without a WordPress.org release after which a component counts as abandoned
+const ABANDONED_AFTER_DAYS: i64 = 730;
+
+/// Security score penalties (see [`Analysis::security_score`])
+const SCORE_PENALTY_OUTDATED_CORE: u32 = 30;
+const SCORE_PENALTY_OUTDATED_THEME: u32 = 15;
+const SCORE_PENALTY_OUTDATED_PLUGIN: u32 = 10;
+const SCORE_PENALTY_UNKNOWN_VERSION: u32 = 5;
+
+/// Oldest PHP branch still receiving security fixes (major, minor)
+///
+/// PHP 8.1 reached end of life on 2025-12-31.
+const PHP_OLDEST_SUPPORTED: (u64, u64) = (8, 2);
+
+/// Oldest server branches still maintained upstream (product, (major, minor))
+///
+/// Products are matched case-insensitively. nginx only maintains its current
+/// stable and mainline branches (nginx.org/en/download.html; 1.28 became
+/// stable on 2025-04-23 and a new stable branch ships every spring, so
+/// revisit this yearly). Apache httpd 2.2 reached end of life on 2017-07-11
+/// (httpd.apache.org).
+const SERVER_OLDEST_SUPPORTED: &[(&str, (u64, u64))] = &[("nginx", (1, 28)), ("apache", (2, 4))];
+
+/// Distribution names in a `Server` header comment, e.g. `nginx/1.18.0 (Ubuntu)`
+///
+/// Distribution packages keep their upstream version but receive security
+/// backports, so an old branch there isn't necessarily unpatched.
+const DISTRO_SERVER_BUILDS: &[&str] = &[
+    "ubuntu",
+    "debian",
+    "centos",
+    "red hat",
+    "rhel",
+    "rocky",
+    "almalinux",
+    "fedora",
+    "amazon",
+    "suse",
+];
+
+/// Minimum security score for each letter grade, best first
+const GRADE_THRESHOLDS: &[(u8, char)] = &[(90, 'A'), (80, 'B'), (70, 'C'), (60, 'D')];
+
 /// Compare two version strings semantically
 /// Returns Ordering::Greater if current > latest (ahead/dev version)
 /// Returns Ordering::Less if current < latest (outdated)
 /// Returns Ordering::Equal if they match
-fn compare_versions(current: &str, latest: &str) -> Ordering {
+pub(crate) fn compare_versions(current: &str, latest: &str) -> Ordering {
     // Parse version parts, handling alpha/beta/rc suffixes
     fn parse_version(v: &str) -> (Vec<u64>, bool) {
+        // Drop build metadata (e.g., 1.0.0+20240101), which doesn't affect ordering
+        let v = v.split('+').next().unwrap_or(v);
+
         // Split off any suffix like -alpha, -beta, -rc
         let pos = v.find(|c: char| c == '-' || c.is_ascii_alphabetic());
         let version_part = match pos {
@@ -23,9 +71,11 @@ fn compare_versions(current: &str, latest: &str) -> Ordering {
         };
         let has_suffix = pos.is_some();
 
+        // Stop at the first non-numeric segment so later segments keep their position
+        // (leading zeros parse naturally: "02" == 2)
         let parts: Vec<u64> = version_part
             .split('.')
-            .filter_map(|p| p.parse().ok())
+            .map_while(|p| p.parse().ok())
             .collect();
 
         (parts, has_suffix)
@@ -55,8 +105,74 @@ fn compare_versions(current: &str, latest: &str) -> Ordering {
     }
 }
 
+/// Parse the date out of a WordPress.org `last_updated` value
+///
+/// Plugins report `2024-01-15 3:45pm GMT`, themes `2024-01-15`; anything
+/// around a valid `YYYY-MM-DD` is ignored.
+pub(crate) fn parse_api_date(raw: &str) -> Option<Date> {
+    let start = raw.find(|c: char| c.is_ascii_digit())?;
+    let mut parts = raw.get(start..start + 10)?.split('-');
+    let year = parts.next()?.parse().ok()?;
+    let month = Month::try_from(parts.next()?.parse::<u8>().ok()?).ok()?;
+    let day = parts.next()?.parse().ok()?;
+    Date::from_calendar_date(year, month, day).ok()
+}
+
+/// Check if a `YYYY-MM-DD` release date is old enough to call the component abandoned
+fn is_abandoned(last_updated: &str, today: Date) -> bool {
+    parse_api_date(last_updated)
+        .is_some_and(|date| today - date > Duration::days(ABANDONED_AFTER_DAYS))
+}
+
+/// Check if a PHP version belongs to a branch that no longer gets security fixes
+fn is_eol_php(version: &str) -> bool {
+    is_older_branch(version, PHP_OLDEST_SUPPORTED)
+}
+
+/// Check if a server version belongs to a branch no longer maintained upstream
+///
+/// Unknown products are never reported as end-of-life.
+fn is_eol_server(product: &str, version: &str) -> bool {
+    SERVER_OLDEST_SUPPORTED
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(product))
+        .is_some_and(|(_, oldest)| is_older_branch(version, *oldest))
+}
+
+/// Check if a `Server` header names a distribution package build
+fn is_distro_build(server: &str) -> bool {
+    let server = server.to_ascii_lowercase();
+    server
+        .split_once('(')
+        .is_some_and(|(_, comment)| DISTRO_SERVER_BUILDS.iter().any(|d| comment.contains(d)))
+}
+
+/// Check if a version's major.minor branch is older than `oldest`
+fn is_older_branch(version: &str, oldest: (u64, u64)) -> bool {
+    let mut parts = version.split('.').map_while(|p| p.parse::<u64>().ok());
+    match (parts.next(), parts.next()) {
+        (Some(major), Some(minor)) => (major, minor) < oldest,
+        _ => false,
+    }
+}
+
+/// Split a `Server` header into product and version
+///
+/// `nginx/1.18.0 (Ubuntu)` gives `("nginx", Some("1.18.0"))`; a bare product
+/// such as `cloudflare` has no version.
+fn parse_server(server: &str) -> (&str, Option<&str>) {
+    let product = server.split_whitespace().next().unwrap_or(server);
+    match product.split_once('/') {
+        Some((name, version)) if version.starts_with(|c: char| c.is_ascii_digit()) => {
+            (name, Some(version))
+        }
+        Some((name, _)) => (name, None),
+        None => (product, None),
+    }
+}
+
 /// Component type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ComponentType {
     /// WordPress core
@@ -65,6 +181,12 @@ pub enum ComponentType {
     Theme,
     /// Plugin
     Plugin,
+    /// Page builder (e.g., Elementor, Divi)
+    Builder,
+    /// PHP runtime
+    Php,
+    /// Web server software
+    Server,
 }
 
 impl std::fmt::Display for ComponentType {
@@ -73,12 +195,26 @@ impl std::fmt::Display for ComponentType {
             Self::Core => write!(f, "Core"),
             Self::Theme => write!(f, "Theme"),
             Self::Plugin => write!(f, "Plugin"),
+            Self::Builder => write!(f, "Builder"),
+            Self::Php => write!(f, "PHP"),
+            Self::Server => write!(f, "Server"),
         }
     }
 }
 
+/// Where a component is distributed from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComponentSource {
+    /// Listed in the WordPress.org directory
+    Directory,
+    /// Not in the WordPress.org directory (premium or custom), so it can't be
+    /// version-checked through the public API
+    Premium,
+}
+
 /// Analysis result for a single component
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComponentAnalysis {
     /// Component type
     pub component_type: ComponentType,
@@ -94,10 +230,38 @@ pub struct ComponentAnalysis {
 
     /// Component status
     pub status: ComponentStatus,
+
+    /// Whether sources disagree on the version (e.g., a cache-buster `?ver=`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disputed: bool,
+
+    /// Whether `version` is an estimated range (e.g., `>=6.0`) rather than exact
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub estimated: bool,
+
+    /// Active installs reported by WordPress.org (plugins only; None if unlisted)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_installs: Option<u64>,
+
+    /// Distribution source (plugins only; None if the lookup failed or was skipped)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<ComponentSource>,
+
+    /// Date of the last WordPress.org release (`YYYY-MM-DD`; plugins and themes)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<String>,
+
+    /// Newest WordPress version the component is tested with, per WordPress.org
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tested_up_to: Option<String>,
+
+    /// Whether the last WordPress.org release is more than two years old
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub abandoned: bool,
 }
 
 impl ComponentAnalysis {
-    fn new(
+    pub(crate) fn new(
         component_type: ComponentType,
         name: impl Into<String>,
         version: Option<String>,
@@ -114,7 +278,8 @@ impl ComponentAnalysis {
         } else {
             match compare_versions(&version_str, &latest_str) {
                 Ordering::Less => ComponentStatus::Outdated,
-                Ordering::Equal | Ordering::Greater => ComponentStatus::Ok,
+                Ordering::Equal => ComponentStatus::Ok,
+                Ordering::Greater => ComponentStatus::Ahead,
             }
         };
 
@@ -124,26 +289,128 @@ impl ComponentAnalysis {
             version: version_str,
             latest_version: latest_str,
             status,
+            disputed: false,
+            estimated: false,
+            active_installs: None,
+            source: None,
+            last_updated: None,
+            tested_up_to: None,
+            abandoned: false,
         }
     }
 
-    fn not_detected(component_type: ComponentType, name: impl Into<String>) -> Self {
+    /// Analysis for a detected plugin, including its WordPress.org details
+    pub(crate) fn for_plugin(plugin: &PluginInfo) -> Self {
+        let mut analysis = Self::new(
+            ComponentType::Plugin,
+            &plugin.slug,
+            plugin.version.clone(),
+            plugin.latest_version.clone(),
+        );
+        analysis.active_installs = plugin.active_installs;
+        analysis.source = plugin.listed.map(|listed| {
+            if listed {
+                ComponentSource::Directory
+            } else {
+                ComponentSource::Premium
+            }
+        });
+        analysis.with_release_details(
+            plugin.last_updated.as_deref(),
+            plugin.tested_up_to.as_deref(),
+        )
+    }
+
+    /// Attach WordPress.org release details, flagging long-unmaintained components
+    fn with_release_details(
+        mut self,
+        last_updated: Option<&str>,
+        tested_up_to: Option<&str>,
+    ) -> Self {
+        let today = OffsetDateTime::now_utc().date();
+        self.abandoned = last_updated.is_some_and(|date| is_abandoned(date, today));
+        self.last_updated = last_updated.map(str::to_string);
+        self.tested_up_to = tested_up_to.map(str::to_string);
+        self
+    }
+
+    /// Check if the component is outdated
+    pub fn is_outdated(&self) -> bool {
+        self.status == ComponentStatus::Outdated
+    }
+
+    /// Check if the component is up to date
+    pub fn is_ok(&self) -> bool {
+        self.status == ComponentStatus::Ok
+    }
+
+    /// Check if the component runs an unreleased version newer than the latest
+    pub fn is_ahead(&self) -> bool {
+        self.status == ComponentStatus::Ahead
+    }
+
+    /// Check if the component was detected but its version is unknown
+    pub fn is_unknown(&self) -> bool {
+        self.status == ComponentStatus::Unknown
+    }
+
+    /// Check if the component was detected at all
+    pub fn is_detected(&self) -> bool {
+        self.status != ComponentStatus::NotDetected
+    }
+
+    /// Check if the component is a premium/custom plugin not on WordPress.org
+    pub fn is_premium(&self) -> bool {
+        self.source == Some(ComponentSource::Premium)
+    }
+
+    /// Latest version for display; `premium` when WordPress.org can't tell
+    pub fn display_latest_version(&self) -> String {
+        if self.is_premium() && self.latest_version == UNKNOWN_VERSION {
+            "premium".to_string()
+        } else {
+            self.latest_version.clone()
+        }
+    }
+
+    /// Version for display, annotated with `(disputed)` when sources disagree
+    /// and `(estimate)` for an estimated range
+    pub fn display_version(&self) -> String {
+        if self.disputed {
+            format!("{} (disputed)", self.version)
+        } else if self.estimated {
+            format!("{} (estimate)", self.version)
+        } else {
+            self.version.clone()
+        }
+    }
+
+    pub(crate) fn not_detected(component_type: ComponentType, name: impl Into<String>) -> Self {
         Self {
             component_type,
             name: name.into(),
             version: UNKNOWN_VERSION.to_string(),
             latest_version: UNKNOWN_VERSION.to_string(),
             status: ComponentStatus::NotDetected,
+            disputed: false,
+            estimated: false,
+            active_installs: None,
+            source: None,
+            last_updated: None,
+            tested_up_to: None,
+            abandoned: false,
         }
     }
 }
 
 /// Component status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ComponentStatus {
     /// Component is up to date
     Ok,
+    /// Component is newer than the latest release (development/beta build)
+    Ahead,
     /// Component detected but version unknown
     Unknown,
     /// Component is outdated
@@ -152,26 +419,151 @@ pub enum ComponentStatus {
     NotDetected,
 }
 
+/// Severity of a security note
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NoteSeverity {
+    /// Informational only
+    Info,
+    /// Low risk
+    Low,
+    /// Medium risk
+    Medium,
+    /// High risk
+    High,
+}
+
+impl std::fmt::Display for NoteSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Info => write!(f, "Info"),
+            Self::Low => write!(f, "Low"),
+            Self::Medium => write!(f, "Medium"),
+            Self::High => write!(f, "High"),
+        }
+    }
+}
+
+/// Worst finding relevant to a CI gate (see [`Analysis::exit_severity`])
+///
+/// Ordered `None < Warning < Critical`, so the worst of several is their maximum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Nothing that fails the gate
+    None,
+    /// Outdated components or unknown versions
+    Warning,
+    /// End-of-life software or high-severity exposures
+    Critical,
+}
+
+/// Findings that should fail a CI gate
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FailOn {
+    /// Never fail (default)
+    #[default]
+    Never,
+    /// Fail on warnings and critical findings
+    Warning,
+    /// Fail only on critical findings
+    Critical,
+}
+
+/// Security note about the site (exposed files, hardening issues)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Note {
+    /// Note severity
+    pub severity: NoteSeverity,
+
+    /// Human-readable description
+    pub message: String,
+}
+
+impl Note {
+    fn new(severity: NoteSeverity, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+        }
+    }
+}
+
 /// Complete analysis results
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Analysis {
     /// Target URL
     pub url: String,
 
+    /// URL the site was served from after redirects
+    pub final_url: String,
+
     /// WordPress core analysis
     pub wordpress: ComponentAnalysis,
 
     /// Main theme analysis
     pub theme: ComponentAnalysis,
 
-    /// Plugin analyses
-    pub plugins: HashMap<String, ComponentAnalysis>,
+    /// Plugin analyses, keyed and ordered by slug
+    pub plugins: BTreeMap<String, ComponentAnalysis>,
+
+    /// Page builder analysis (None if no known builder was detected)
+    #[serde(default)]
+    pub page_builder: Option<ComponentAnalysis>,
+
+    /// Whether the site is a multisite network (None if unknown)
+    pub is_multisite: Option<bool>,
+
+    /// Site locale in WordPress form (e.g., `de_DE`; None if not found)
+    #[serde(default)]
+    pub locale: Option<String>,
+
+    /// PHP version exposed by the server (None if not exposed)
+    pub php_version: Option<String>,
+
+    /// PHP runtime analysis (None if the version is not exposed)
+    #[serde(default)]
+    pub php: Option<ComponentAnalysis>,
+
+    /// Server software analysis (None if the `Server` header is absent)
+    #[serde(default)]
+    pub server: Option<ComponentAnalysis>,
+
+    /// Security notes, most severe first
+    pub notes: Vec<Note>,
+
+    /// How far to trust the detection (see [`ScanResult::confidence`])
+    #[serde(default)]
+    pub confidence: Confidence,
+
+    /// How each detection was made
+    #[serde(default)]
+    pub evidence: Vec<Evidence>,
+
+    /// Namespaces listed by the REST API root
+    #[serde(default)]
+    pub rest_namespaces: Vec<String>,
+
+    /// Security score from 0 to 100 (see [`Analysis::security_score`])
+    pub security_score: u8,
+
+    /// Letter grade for the security score (A-F)
+    pub grade: char,
 }
 
 impl Analysis {
     /// Check if WordPress was detected
     pub fn is_wordpress(&self) -> bool {
-        self.wordpress.status != ComponentStatus::NotDetected
+        self.wordpress.is_detected()
+    }
+
+    /// Iterate over core, theme, and plugins, in that order
+    ///
+    /// Plugins come sorted by slug. Optional rows (page builder, PHP, server)
+    /// are left out; they don't count toward the score or issue checks.
+    pub fn components(&self) -> impl Iterator<Item = &ComponentAnalysis> {
+        [&self.wordpress, &self.theme]
+            .into_iter()
+            .chain(self.plugins.values())
     }
 
     /// Get count of detected plugins
@@ -181,15 +573,89 @@ impl Analysis {
 
     /// Get count of outdated components
     pub fn outdated_count(&self) -> usize {
-        let core_outdated = (self.wordpress.status == ComponentStatus::Outdated) as usize;
-        let theme_outdated = (self.theme.status == ComponentStatus::Outdated) as usize;
-        let plugins_outdated = self
-            .plugins
-            .values()
-            .filter(|p| p.status == ComponentStatus::Outdated)
-            .count();
+        self.components().filter(|c| c.is_outdated()).count()
+    }
+
+    /// Check if any component is outdated or has an unknown version
+    pub fn has_issues(&self) -> bool {
+        self.components().any(|c| c.is_outdated() || c.is_unknown())
+    }
+
+    /// Worst finding that fails a CI gate with the given policy
+    ///
+    /// High-severity notes (e.g., end-of-life PHP, exposed `debug.log`) are
+    /// critical; outdated components and unknown versions are a warning.
+    /// Findings below the `fail_on` threshold count as [`Severity::None`].
+    pub fn exit_severity(&self, fail_on: FailOn) -> Severity {
+        let severity = if self
+            .notes
+            .iter()
+            .any(|note| note.severity >= NoteSeverity::High)
+        {
+            Severity::Critical
+        } else if self.has_issues() {
+            Severity::Warning
+        } else {
+            Severity::None
+        };
+
+        let threshold = match fail_on {
+            FailOn::Never => return Severity::None,
+            FailOn::Warning => Severity::Warning,
+            FailOn::Critical => Severity::Critical,
+        };
+        if severity >= threshold {
+            severity
+        } else {
+            Severity::None
+        }
+    }
+
+    /// Compute a security score from 0 (worst) to 100 (fully up to date)
+    ///
+    /// Starts at 100 and subtracts a penalty per component:
+    /// outdated core 30, outdated theme 15, outdated plugin 10, and
+    /// unknown version 5 (any component type). The result is clamped at 0.
+    pub fn security_score(&self) -> u8 {
+        let penalty: u32 = self
+            .components()
+            .map(|c| match (c.status, c.component_type) {
+                (ComponentStatus::Outdated, ComponentType::Core) => SCORE_PENALTY_OUTDATED_CORE,
+                (ComponentStatus::Outdated, ComponentType::Theme) => SCORE_PENALTY_OUTDATED_THEME,
+                (ComponentStatus::Outdated, ComponentType::Plugin) => SCORE_PENALTY_OUTDATED_PLUGIN,
+                (ComponentStatus::Unknown, _) => SCORE_PENALTY_UNKNOWN_VERSION,
+                _ => 0,
+            })
+            .sum();
+
+        100u32.saturating_sub(penalty) as u8
+    }
+
+    /// Serialize the analysis to pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
 
-        core_outdated + theme_outdated + plugins_outdated
+    /// Load an analysis from JSON
+    ///
+    /// Accepts both a bare analysis (as produced by [`Analysis::to_json`]) and
+    /// the single-site envelope written by `--output json`.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let mut value: serde_json::Value =
+            serde_json::from_str(json).map_err(Error::DeserializationFailed)?;
+        if let Some(analysis) = value.get_mut("analysis") {
+            value = analysis.take();
+        }
+        serde_json::from_value(value).map_err(Error::DeserializationFailed)
+    }
+
+    /// Letter grade (A-F) for a security score
+    pub fn grade_for(score: u8) -> char {
+        GRADE_THRESHOLDS
+            .iter()
+            .find(|(min, _)| score >= *min)
+            .map(|(_, grade)| *grade)
+            .unwrap_or('F')
     }
 }
 
@@ -206,12 +672,28 @@ impl Analyzer {
 
     /// Perform the analysis
     pub fn analyze(self) -> Analysis {
-        Analysis {
+        let mut analysis = Analysis {
             url: self.scan.url.to_string(),
+            final_url: self.scan.final_url.to_string(),
             wordpress: self.analyze_wordpress(),
             theme: self.analyze_theme(),
             plugins: self.analyze_plugins(),
-        }
+            page_builder: self.analyze_page_builder(),
+            is_multisite: self.scan.is_multisite,
+            locale: self.scan.locale.clone(),
+            php_version: self.scan.php_version.clone(),
+            php: self.analyze_php(),
+            server: self.analyze_server(),
+            notes: self.analyze_notes(),
+            confidence: self.scan.confidence(),
+            evidence: self.scan.evidence.clone(),
+            rest_namespaces: self.scan.rest_namespaces.clone(),
+            security_score: 0,
+            grade: 'F',
+        };
+        analysis.security_score = analysis.security_score();
+        analysis.grade = Analysis::grade_for(analysis.security_score);
+        analysis
     }
 
     fn analyze_wordpress(&self) -> ComponentAnalysis {
@@ -224,12 +706,18 @@ impl Analyzer {
             ),
             None if self.scan.wordpress_detected => {
                 // WordPress detected via REST API or cookies, but version unknown
-                ComponentAnalysis::new(
+                let mut analysis = ComponentAnalysis::new(
                     ComponentType::Core,
                     "WordPress",
                     None,
                     self.scan.wordpress_latest.clone(),
-                )
+                );
+                // A lower bound can't prove the site up to date, so the status stays Unknown
+                if let Some(estimate) = &self.scan.wordpress_version_estimate {
+                    analysis.version = estimate.clone();
+                    analysis.estimated = true;
+                }
+                analysis
             }
             None => ComponentAnalysis::not_detected(ComponentType::Core, "WordPress"),
         }
@@ -237,29 +725,810 @@ impl Analyzer {
 
     fn analyze_theme(&self) -> ComponentAnalysis {
         match &self.scan.theme {
-            Some(theme) => ComponentAnalysis::new(
-                ComponentType::Theme,
-                &theme.slug,
-                theme.version.clone(),
-                theme.latest_version.clone(),
-            ),
+            Some(theme) => {
+                let mut analysis = ComponentAnalysis::new(
+                    ComponentType::Theme,
+                    &theme.slug,
+                    theme.version.clone(),
+                    theme.latest_version.clone(),
+                );
+                analysis.disputed = theme.disputed_version.is_some();
+                analysis.with_release_details(
+                    theme.last_updated.as_deref(),
+                    theme.tested_up_to.as_deref(),
+                )
+            }
             None => ComponentAnalysis::not_detected(ComponentType::Theme, "-"),
         }
     }
 
-    fn analyze_plugins(&self) -> HashMap<String, ComponentAnalysis> {
+    /// Page builder row; there is no latest version to compare against
+    fn analyze_page_builder(&self) -> Option<ComponentAnalysis> {
+        let builder = self.scan.page_builder.as_ref()?;
+        Some(ComponentAnalysis::new(
+            ComponentType::Builder,
+            &builder.name,
+            builder.version.clone(),
+            None,
+        ))
+    }
+
+    /// PHP row; end-of-life branches are reported as outdated
+    fn analyze_php(&self) -> Option<ComponentAnalysis> {
+        let version = self.scan.php_version.as_ref()?;
+        let mut php =
+            ComponentAnalysis::new(ComponentType::Php, "PHP", Some(version.clone()), None);
+        if is_eol_php(version) {
+            php.status = ComponentStatus::Outdated;
+        }
+        Some(php)
+    }
+
+    /// Server row; branches no longer maintained upstream are reported as outdated
+    fn analyze_server(&self) -> Option<ComponentAnalysis> {
+        let (product, version) = parse_server(self.scan.server.as_ref()?);
+        let mut server = ComponentAnalysis::new(
+            ComponentType::Server,
+            product,
+            version.map(str::to_string),
+            None,
+        );
+        let distro = self.scan.server.as_deref().is_some_and(is_distro_build);
+        if !distro && version.is_some_and(|version| is_eol_server(product, version)) {
+            server.status = ComponentStatus::Outdated;
+        }
+        Some(server)
+    }
+
+    fn analyze_notes(&self) -> Vec<Note> {
+        let mut notes = Vec::new();
+
+        if self.scan.partial {
+            notes.push(Note::new(
+                NoteSeverity::Info,
+                format!(
+                    "Homepage could not be fetched; results are partial ({} failed probe(s): {})",
+                    self.scan.failed_probes.len(),
+                    self.scan.failed_probes.join("; ")
+                ),
+            ));
+        }
+
+        if self.scan.truncated {
+            notes.push(Note::new(
+                NoteSeverity::Info,
+                "Request budget exhausted; later probes and lookups were skipped",
+            ));
+        }
+
+        if self.scan.maintenance_mode == Some(true) {
+            notes.push(Note::new(
+                NoteSeverity::Info,
+                "Homepage is a maintenance or coming-soon page; results may not reflect the real site",
+            ));
+        }
+
+        if self.scan.api_rate_limited > 0 {
+            notes.push(Note::new(
+                NoteSeverity::Info,
+                format!(
+                    "WordPress.org API rate limited {} lookup(s); some latest versions are missing",
+                    self.scan.api_rate_limited
+                ),
+            ));
+        }
+
+        if let Some(woocommerce) = &self.scan.woocommerce {
+            let version = self
+                .scan
+                .plugins
+                .iter()
+                .find(|p| p.slug == "woocommerce")
+                .and_then(|p| p.version.as_deref())
+                .unwrap_or(UNKNOWN_VERSION);
+            notes.push(Note::new(
+                NoteSeverity::Info,
+                format!("WooCommerce store detected (version {})", version),
+            ));
+            if woocommerce.store_api_public {
+                notes.push(Note::new(
+                    NoteSeverity::Low,
+                    "WooCommerce Store API lists products without authentication",
+                ));
+            }
+        }
+
+        if let Some(theme) = &self.scan.theme
+            && let (Some(version), Some(style_version)) = (&theme.version, &theme.disputed_version)
+        {
+            notes.push(Note::new(
+                NoteSeverity::Info,
+                format!(
+                    "Theme {} is enqueued as version {} but style.css says {}; the reported version may be a cache-buster",
+                    theme.slug, version, style_version
+                ),
+            ));
+        }
+
+        let premium: Vec<&str> = self
+            .scan
+            .plugins
+            .iter()
+            .filter(|plugin| plugin.listed == Some(false))
+            .map(|plugin| plugin.slug.as_str())
+            .collect();
+        if !premium.is_empty() {
+            notes.push(Note::new(
+                NoteSeverity::Info,
+                format!(
+                    "{} plugin(s) not on WordPress.org (premium or custom) can't be version-checked: {}",
+                    premium.len(),
+                    premium.join(", ")
+                ),
+            ));
+        }
+
+        let today = OffsetDateTime::now_utc().date();
+        let abandoned = self
+            .scan
+            .theme
+            .iter()
+            .map(|theme| ("Theme", &theme.slug, &theme.last_updated))
+            .chain(
+                self.scan
+                    .plugins
+                    .iter()
+                    .map(|plugin| ("Plugin", &plugin.slug, &plugin.last_updated)),
+            );
+        for (kind, slug, last_updated) in abandoned {
+            if let Some(date) = last_updated
+                && is_abandoned(date, today)
+            {
+                notes.push(Note::new(
+                    NoteSeverity::Medium,
+                    format!(
+                        "{} {} has had no WordPress.org release since {} and may be abandoned",
+                        kind, slug, date
+                    ),
+                ));
+            }
+        }
+
+        if self.scan.is_multisite == Some(true) {
+            notes.push(Note::new(
+                NoteSeverity::Info,
+                "Multisite network: plugins may be network-activated and updated centrally",
+            ));
+        }
+
+        if let Some(cdn_host) = &self.scan.cdn_host {
+            notes.push(Note::new(
+                NoteSeverity::Info,
+                format!("wp-content assets are served from {}", cdn_host),
+            ));
+        }
+
+        if let Some(cdn) = &self.scan.cdn {
+            notes.push(Note::new(
+                NoteSeverity::Info,
+                format!(
+                    "Site is behind {}; WordPress cookies and headers may be masked",
+                    cdn
+                ),
+            ));
+        }
+
+        if let Some(content_dir) = &self.scan.content_dir {
+            notes.push(Note::new(
+                NoteSeverity::Info,
+                format!(
+                    "Content directory renamed from wp-content to /{}/",
+                    content_dir
+                ),
+            ));
+        }
+
+        if self.scan.rest_api_status == Some(RestApiStatus::Restricted) {
+            notes.push(Note::new(
+                NoteSeverity::Info,
+                "REST API requires authentication (hardened)",
+            ));
+        }
+
+        if let Some(php_version) = &self.scan.php_version {
+            if is_eol_php(php_version) {
+                notes.push(Note::new(
+                    NoteSeverity::High,
+                    format!(
+                        "PHP {} is end-of-life and no longer receives security fixes",
+                        php_version
+                    ),
+                ));
+            } else {
+                notes.push(Note::new(
+                    NoteSeverity::Low,
+                    format!("PHP version {} is exposed via X-Powered-By", php_version),
+                ));
+            }
+        }
+
+        if let Some(server) = &self.scan.server
+            && let (product, Some(version)) = parse_server(server)
+        {
+            if is_eol_server(product, version) && is_distro_build(server) {
+                notes.push(Note::new(
+                    NoteSeverity::Info,
+                    format!(
+                        "{} {} is an old upstream branch, but distribution packages receive security backports",
+                        product, version
+                    ),
+                ));
+            } else if is_eol_server(product, version) {
+                notes.push(Note::new(
+                    NoteSeverity::High,
+                    format!("{} {} is no longer maintained upstream", product, version),
+                ));
+            } else {
+                notes.push(Note::new(
+                    NoteSeverity::Low,
+                    format!(
+                        "Server version {} {} is exposed via the Server header",
+                        product, version
+                    ),
+                ));
+            }
+        }
+
+        if self.scan.login_accessible == Some(true) {
+            notes.push(Note::new(
+                NoteSeverity::Medium,
+                "Default login page (wp-login.php) is publicly accessible",
+            ));
+        }
+
+        if self.scan.login_relocated == Some(true) {
+            let message = match &self.scan.login_url {
+                Some(login_url) => format!("Login page moved to a custom URL ({})", login_url),
+                None => "Login page moved off wp-login.php".to_string(),
+            };
+            notes.push(Note::new(NoteSeverity::Info, message));
+        }
+
+        if self.scan.wp_cron_exposed == Some(true) {
+            notes.push(Note::new(
+                NoteSeverity::Low,
+                "wp-cron.php can be triggered externally (consider a system cron instead)",
+            ));
+        }
+
+        match self.scan.file_editor_blocked {
+            Some(true) => notes.push(Note::new(
+                NoteSeverity::Info,
+                "Theme editor is blocked, so file editing is likely disabled (low confidence)",
+            )),
+            Some(false) => notes.push(Note::new(
+                NoteSeverity::Info,
+                "Theme editor is only guarded by the login page; DISALLOW_FILE_EDIT can't be confirmed remotely (low confidence)",
+            )),
+            None => {}
+        }
+
+        if self.scan.debug_log_exposed == Some(true) {
+            notes.push(Note::new(
+                NoteSeverity::High,
+                "wp-content/debug.log is publicly readable",
+            ));
+        }
+
+        if !self.scan.exposed_backups.is_empty() {
+            notes.push(Note::new(
+                NoteSeverity::High,
+                format!(
+                    "Backup file(s) publicly accessible: {}",
+                    self.scan.exposed_backups.join(", ")
+                ),
+            ));
+        }
+
+        notes.sort_by_key(|note| std::cmp::Reverse(note.severity));
+        notes
+    }
+
+    fn analyze_plugins(&self) -> BTreeMap<String, ComponentAnalysis> {
         self.scan
             .plugins
             .iter()
-            .map(|plugin| {
-                let analysis = ComponentAnalysis::new(
-                    ComponentType::Plugin,
-                    &plugin.slug,
-                    plugin.version.clone(),
-                    plugin.latest_version.clone(),
-                );
-                (plugin.slug.clone(), analysis)
-            })
+            .map(|plugin| (plugin.slug.clone(), ComponentAnalysis::for_plugin(plugin)))
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::{DetectionMethod, PageBuilderInfo, ThemeInfo};
+    use url::Url;
+
+    fn scan_with_plugins(slugs: &[&str]) -> ScanResult {
+        ScanResult {
+            wordpress_detected: true,
+            wordpress_version: Some("6.4.2".to_string()),
+            wordpress_latest: Some("6.4.2".to_string()),
+            theme: None,
+            plugins: slugs
+                .iter()
+                .map(|slug| PluginInfo {
+                    slug: slug.to_string(),
+                    version: Some("1.0.0".to_string()),
+                    latest_version: Some("1.1.0".to_string()),
+                    active_installs: None,
+                    listed: None,
+                    last_updated: None,
+                    tested_up_to: None,
+                })
+                .collect(),
+            ..ScanResult::empty(Url::parse("https://example.com/").unwrap())
+        }
+    }
+
+    #[test]
+    fn php_version_notes() {
+        assert!(is_eol_php("7.4.33"));
+        assert!(is_eol_php("8.1.27"));
+        assert!(!is_eol_php("8.2.0"));
+        assert!(!is_eol_php("8.3"));
+        assert!(!is_eol_php("garbage"));
+
+        let mut scan = scan_with_plugins(&[]);
+        scan.php_version = Some("7.4.33".to_string());
+        let analysis = Analyzer::new(scan).analyze();
+        assert_eq!(analysis.php_version.as_deref(), Some("7.4.33"));
+        assert_eq!(analysis.notes[0].severity, NoteSeverity::High);
+        assert!(analysis.notes[0].message.contains("end-of-life"));
+        let php = analysis.php.as_ref().unwrap();
+        assert_eq!(php.component_type, ComponentType::Php);
+        assert!(php.is_outdated());
+
+        let mut scan = scan_with_plugins(&[]);
+        scan.php_version = Some("8.3.4".to_string());
+        let analysis = Analyzer::new(scan).analyze();
+        assert_eq!(analysis.notes[0].severity, NoteSeverity::Low);
+        assert!(analysis.php.as_ref().unwrap().is_ok());
+
+        let analysis = Analyzer::new(scan_with_plugins(&[])).analyze();
+        assert!(analysis.php.is_none());
+    }
+
+    #[test]
+    fn server_header_row_and_notes() {
+        assert_eq!(
+            parse_server("Apache/2.4.41 (Ubuntu)"),
+            ("Apache", Some("2.4.41"))
+        );
+        assert_eq!(parse_server("cloudflare"), ("cloudflare", None));
+        assert!(is_eol_server("Apache", "2.2.34"));
+        assert!(!is_eol_server("LiteSpeed", "1.0"));
+
+        let mut scan = scan_with_plugins(&[]);
+        scan.server = Some("nginx/1.18.0".to_string());
+        let analysis = Analyzer::new(scan).analyze();
+        let server = analysis.server.as_ref().unwrap();
+        assert_eq!(server.component_type, ComponentType::Server);
+        assert_eq!(server.name, "nginx");
+        assert!(server.is_outdated());
+        assert_eq!(analysis.notes[0].severity, NoteSeverity::High);
+
+        // Distribution packages get backports under the old version number
+        let mut scan = scan_with_plugins(&[]);
+        scan.server = Some("nginx/1.18.0 (Ubuntu)".to_string());
+        let analysis = Analyzer::new(scan).analyze();
+        assert!(!analysis.server.as_ref().unwrap().is_outdated());
+        assert_eq!(analysis.notes[0].severity, NoteSeverity::Info);
+        assert!(!is_distro_build("Apache/2.2.34 (Unix)"));
+
+        let mut scan = scan_with_plugins(&[]);
+        scan.server = Some("cloudflare".to_string());
+        let analysis = Analyzer::new(scan).analyze();
+        assert!(analysis.server.as_ref().unwrap().is_unknown());
+        assert!(
+            analysis
+                .notes
+                .iter()
+                .all(|n| !n.message.contains("cloudflare"))
+        );
+
+        let analysis = Analyzer::new(scan_with_plugins(&[])).analyze();
+        assert!(analysis.server.is_none());
+    }
+
+    #[test]
+    fn maintenance_mode_note() {
+        let mut scan = scan_with_plugins(&[]);
+        scan.maintenance_mode = Some(true);
+        let analysis = Analyzer::new(scan).analyze();
+        assert!(
+            analysis
+                .notes
+                .iter()
+                .any(|note| note.message.contains("maintenance or coming-soon page"))
+        );
+
+        let mut scan = scan_with_plugins(&[]);
+        scan.maintenance_mode = Some(false);
+        assert!(Analyzer::new(scan).analyze().notes.is_empty());
+    }
+
+    #[test]
+    fn abandoned_components() {
+        let today = Date::from_calendar_date(2026, Month::October, 16).unwrap();
+        assert_eq!(
+            parse_api_date("2024-01-15 3:45pm GMT"),
+            Date::from_calendar_date(2024, Month::January, 15).ok()
+        );
+        assert!(parse_api_date("2024-13-01").is_none());
+        assert!(parse_api_date("yesterday").is_none());
+        assert!(is_abandoned("2019-03-02 1:10am GMT", today));
+        assert!(!is_abandoned("2025-06-01", today));
+        assert!(!is_abandoned("garbage", today));
+
+        let mut scan = scan_with_plugins(&["hello-dolly", "akismet"]);
+        scan.plugins[0].last_updated = Some("2015-01-01".to_string());
+        scan.plugins[0].tested_up_to = Some("4.1".to_string());
+        scan.plugins[1].last_updated = Some(OffsetDateTime::now_utc().date().to_string());
+        let analysis = Analyzer::new(scan).analyze();
+
+        let abandoned = &analysis.plugins["hello-dolly"];
+        assert!(abandoned.abandoned);
+        assert_eq!(abandoned.tested_up_to.as_deref(), Some("4.1"));
+        assert!(!analysis.plugins["akismet"].abandoned);
+        assert_eq!(analysis.notes[0].severity, NoteSeverity::Medium);
+        assert!(analysis.notes[0].message.contains("hello-dolly"));
+        assert!(analysis.to_json().unwrap().contains("\"abandoned\": true"));
+    }
+
+    #[test]
+    fn exit_severity_respects_fail_on() {
+        let outdated = Analyzer::new(scan_with_plugins(&["akismet"])).analyze();
+        assert_eq!(outdated.exit_severity(FailOn::Never), Severity::None);
+        assert_eq!(outdated.exit_severity(FailOn::Warning), Severity::Warning);
+        assert_eq!(outdated.exit_severity(FailOn::Critical), Severity::None);
+
+        let mut scan = scan_with_plugins(&["akismet"]);
+        scan.php_version = Some("7.4.33".to_string());
+        let critical = Analyzer::new(scan).analyze();
+        assert_eq!(critical.exit_severity(FailOn::Warning), Severity::Critical);
+        assert_eq!(critical.exit_severity(FailOn::Critical), Severity::Critical);
+
+        let clean = Analyzer::new(scan_with_plugins(&[])).analyze();
+        assert_eq!(clean.exit_severity(FailOn::Warning), Severity::None);
+        assert!(Severity::None < Severity::Warning && Severity::Warning < Severity::Critical);
+    }
+
+    #[test]
+    fn estimated_core_version() {
+        let mut scan = scan_with_plugins(&[]);
+        scan.wordpress_version = None;
+        scan.wordpress_version_estimate = Some(">=6.0".to_string());
+        let analysis = Analyzer::new(scan).analyze();
+        assert_eq!(analysis.wordpress.version, ">=6.0");
+        assert!(analysis.wordpress.estimated && analysis.wordpress.is_unknown());
+        assert_eq!(analysis.wordpress.display_version(), ">=6.0 (estimate)");
+        assert!(analysis.to_json().unwrap().contains("\"estimated\": true"));
+    }
+
+    #[test]
+    fn exposed_backups_note() {
+        let mut scan = scan_with_plugins(&[]);
+        scan.exposed_backups = vec!["wp-config.php.bak".to_string(), "backup.zip".to_string()];
+        let analysis = Analyzer::new(scan).analyze();
+        assert_eq!(analysis.notes[0].severity, NoteSeverity::High);
+        assert_eq!(
+            analysis.notes[0].message,
+            "Backup file(s) publicly accessible: wp-config.php.bak, backup.zip"
+        );
+    }
+
+    #[test]
+    fn components_yield_core_theme_then_plugins() {
+        let mut scan = scan_with_plugins(&["wordfence", "akismet"]);
+        scan.php_version = Some("8.2.1".to_string());
+        let analysis = Analyzer::new(scan).analyze();
+
+        let names: Vec<(ComponentType, &str)> = analysis
+            .components()
+            .map(|c| (c.component_type, c.name.as_str()))
+            .collect();
+        assert_eq!(names.len(), 4);
+        assert_eq!(names[0].0, ComponentType::Core);
+        assert_eq!(names[1].0, ComponentType::Theme);
+        assert_eq!(
+            names[2..],
+            [
+                (ComponentType::Plugin, "akismet"),
+                (ComponentType::Plugin, "wordfence")
+            ]
+        );
+    }
+
+    #[test]
+    fn relocated_login_note() {
+        let mut scan = scan_with_plugins(&[]);
+        scan.login_relocated = Some(true);
+        scan.login_url = Some("https://example.com/secret-login/".to_string());
+        let analysis = Analyzer::new(scan).analyze();
+        assert!(analysis.notes.iter().any(|note| {
+            note.severity == NoteSeverity::Info
+                && note.message
+                    == "Login page moved to a custom URL (https://example.com/secret-login/)"
+        }));
+    }
+
+    #[test]
+    fn disputed_theme_version() {
+        let mut scan = scan_with_plugins(&[]);
+        scan.theme = Some(ThemeInfo {
+            slug: "astra".to_string(),
+            version: Some("7.2".to_string()),
+            latest_version: Some("4.6.3".to_string()),
+            disputed_version: Some("4.6.3".to_string()),
+            last_updated: None,
+            tested_up_to: None,
+        });
+        let analysis = Analyzer::new(scan).analyze();
+        assert!(analysis.theme.disputed);
+        assert_eq!(analysis.theme.display_version(), "7.2 (disputed)");
+        assert!(
+            analysis
+                .notes
+                .iter()
+                .any(|note| note.message.contains("style.css says 4.6.3"))
+        );
+        assert!(!analysis.wordpress.disputed);
+    }
+
+    #[test]
+    fn page_builder_row() {
+        let mut scan = scan_with_plugins(&[]);
+        scan.page_builder = Some(PageBuilderInfo {
+            name: "Elementor".to_string(),
+            version: Some("3.18.3".to_string()),
+        });
+        let analysis = Analyzer::new(scan).analyze();
+        let builder = analysis.page_builder.as_ref().unwrap();
+        assert_eq!(builder.component_type, ComponentType::Builder);
+        assert_eq!(builder.name, "Elementor");
+        assert!(builder.is_ok());
+
+        let analysis = Analyzer::new(scan_with_plugins(&[])).analyze();
+        assert!(analysis.page_builder.is_none());
+    }
+
+    #[test]
+    fn confidence_counts_independent_signals() {
+        let mut scan = scan_with_plugins(&["akismet"]);
+        assert_eq!(scan.confidence(), Confidence::Low);
+
+        scan.detection_methods.insert(DetectionMethod::AssetPaths);
+        assert_eq!(scan.confidence(), Confidence::Low);
+
+        scan.detection_methods.insert(DetectionMethod::Version);
+        assert_eq!(scan.confidence(), Confidence::Medium);
+
+        scan.detection_methods.insert(DetectionMethod::RestApi);
+        assert_eq!(scan.confidence(), Confidence::High);
+
+        // Detected without a version, from a cookie alone
+        let mut scan = scan_with_plugins(&[]);
+        scan.wordpress_version = None;
+        scan.detection_methods.insert(DetectionMethod::Markers);
+        let analysis = Analyzer::new(scan).analyze();
+        assert_eq!(analysis.confidence, Confidence::Low);
+    }
+
+    #[test]
+    fn premium_plugins_are_marked() {
+        let mut scan = scan_with_plugins(&["akismet", "gravityforms"]);
+        scan.plugins[0].listed = Some(true);
+        scan.plugins[1].latest_version = None;
+        scan.plugins[1].listed = Some(false);
+        let analysis = Analyzer::new(scan).analyze();
+
+        let premium = &analysis.plugins["gravityforms"];
+        assert!(premium.is_premium());
+        assert_eq!(premium.display_latest_version(), "premium");
+        assert_eq!(
+            analysis.plugins["akismet"].source,
+            Some(ComponentSource::Directory)
+        );
+        assert!(
+            analysis
+                .notes
+                .iter()
+                .any(|note| note.message.ends_with("version-checked: gravityforms"))
+        );
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut scan = scan_with_plugins(&["akismet", "wpforms"]);
+        scan.debug_log_exposed = Some(true);
+        scan.is_multisite = Some(false);
+        let analysis = Analyzer::new(scan).analyze();
+
+        let json = analysis.to_json().unwrap();
+        assert!(json.contains(r#""status": "notdetected""#));
+        assert!(json.contains(r#""component_type": "plugin""#));
+
+        let loaded = Analysis::from_json(&json).unwrap();
+        assert_eq!(loaded.to_json().unwrap(), json);
+        assert_eq!(loaded.theme.status, ComponentStatus::NotDetected);
+        assert_eq!(
+            loaded.plugins["wpforms"].component_type,
+            ComponentType::Plugin
+        );
+        assert_eq!(loaded.notes[0].severity, NoteSeverity::High);
+
+        // Saved `--output json` envelopes load too
+        let envelope = format!(r#"{{"schema_version":1,"generated_at":"x","analysis":{json}}}"#);
+        let loaded = Analysis::from_json(&envelope).unwrap();
+        assert_eq!(loaded.to_json().unwrap(), json);
+
+        assert!(matches!(
+            Analysis::from_json("{}"),
+            Err(Error::DeserializationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn baseline_without_evidence_loads() {
+        let analysis = Analyzer::new(scan_with_plugins(&["akismet"])).analyze();
+        let mut value: serde_json::Value =
+            serde_json::from_str(&analysis.to_json().unwrap()).unwrap();
+        // Baselines saved before evidence was recorded
+        value.as_object_mut().unwrap().remove("evidence");
+
+        let loaded = Analysis::from_json(&value.to_string()).unwrap();
+        assert!(loaded.evidence.is_empty());
+        assert_eq!(loaded.plugins["akismet"].version, "1.0.0");
+    }
+
+    #[test]
+    fn json_output_is_deterministic() {
+        let first = Analyzer::new(scan_with_plugins(&["zeta", "alpha", "mid"])).analyze();
+        let second = Analyzer::new(scan_with_plugins(&["mid", "zeta", "alpha"])).analyze();
+
+        let first_json = serde_json::to_string(&first).unwrap();
+        let second_json = serde_json::to_string(&second).unwrap();
+        assert_eq!(first_json, second_json);
+
+        let names: Vec<&str> = first.plugins.keys().map(String::as_str).collect();
+        assert_eq!(names, vec!["alpha", "mid", "zeta"]);
+        assert_eq!(first.plugin_count(), 3);
+        assert_eq!(first.outdated_count(), 3);
+    }
+
+    #[test]
+    fn compare_versions_segments() {
+        assert_eq!(compare_versions("6.4", "6.4.2"), Ordering::Less);
+        assert_eq!(compare_versions("6.4.2", "6.4"), Ordering::Greater);
+        assert_eq!(compare_versions("6.4", "6.4.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.2.3.4", "1.2.3"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0.0", "1.0.0.1"), Ordering::Less);
+        assert_eq!(compare_versions("1.10", "1.9"), Ordering::Greater);
+    }
+
+    #[test]
+    fn compare_versions_leading_zeros() {
+        assert_eq!(compare_versions("1.02", "1.2"), Ordering::Equal);
+        assert_eq!(compare_versions("1.02", "1.3"), Ordering::Less);
+        assert_eq!(compare_versions("2024.01.05", "2024.1.5"), Ordering::Equal);
+    }
+
+    #[test]
+    fn compare_versions_suffixes_and_metadata() {
+        assert_eq!(compare_versions("7.0-alpha", "7.0"), Ordering::Less);
+        assert_eq!(compare_versions("7.0", "7.0-beta1"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0.0+build5", "1.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.0.0+build5", "1.0.1"), Ordering::Less);
+    }
+
+    #[test]
+    fn component_status_predicates() {
+        let component = |status| ComponentAnalysis {
+            component_type: ComponentType::Plugin,
+            name: "test".to_string(),
+            version: "1.0".to_string(),
+            latest_version: "1.0".to_string(),
+            status,
+            disputed: false,
+            estimated: false,
+            active_installs: None,
+            source: None,
+            last_updated: None,
+            tested_up_to: None,
+            abandoned: false,
+        };
+
+        let ok = component(ComponentStatus::Ok);
+        assert!(ok.is_ok() && ok.is_detected());
+        assert!(!ok.is_outdated() && !ok.is_unknown());
+
+        let outdated = component(ComponentStatus::Outdated);
+        assert!(outdated.is_outdated() && outdated.is_detected());
+        assert!(!outdated.is_ok() && !outdated.is_unknown());
+
+        let unknown = component(ComponentStatus::Unknown);
+        assert!(unknown.is_unknown() && unknown.is_detected());
+        assert!(!unknown.is_ok() && !unknown.is_outdated());
+
+        let ahead = component(ComponentStatus::Ahead);
+        assert!(ahead.is_ahead() && ahead.is_detected());
+        assert!(!ahead.is_ok() && !ahead.is_outdated() && !ahead.is_unknown());
+
+        let missing = component(ComponentStatus::NotDetected);
+        assert!(!missing.is_detected());
+        assert!(!missing.is_ok() && !missing.is_outdated() && !missing.is_unknown());
+    }
+
+    #[test]
+    fn ahead_of_latest_status() {
+        let mut scan = scan_with_plugins(&[]);
+        scan.wordpress_version = Some("6.5-beta1".to_string());
+        scan.wordpress_latest = Some("6.4.2".to_string());
+        let analysis = Analyzer::new(scan).analyze();
+
+        assert_eq!(analysis.wordpress.status, ComponentStatus::Ahead);
+        assert_eq!(analysis.outdated_count(), 0);
+        assert!(!analysis.has_issues());
+        assert_eq!(
+            serde_json::to_value(analysis.wordpress.status).unwrap(),
+            "ahead"
+        );
+    }
+
+    #[test]
+    fn has_issues() {
+        // Up-to-date core, no theme or plugins
+        let analysis = Analyzer::new(scan_with_plugins(&[])).analyze();
+        assert!(!analysis.has_issues());
+
+        // Outdated plugin
+        let analysis = Analyzer::new(scan_with_plugins(&["a"])).analyze();
+        assert!(analysis.has_issues());
+
+        // Unknown core version
+        let mut scan = scan_with_plugins(&[]);
+        scan.wordpress_version = None;
+        let analysis = Analyzer::new(scan).analyze();
+        assert!(analysis.has_issues());
+    }
+
+    #[test]
+    fn security_score_penalties() {
+        // Up-to-date core, no theme, no plugins
+        let analysis = Analyzer::new(scan_with_plugins(&[])).analyze();
+        assert_eq!(analysis.security_score, 100);
+        assert_eq!(analysis.grade, 'A');
+
+        // Three outdated plugins
+        let analysis = Analyzer::new(scan_with_plugins(&["a", "b", "c"])).analyze();
+        assert_eq!(analysis.security_score, 70);
+        assert_eq!(analysis.grade, 'C');
+
+        // Outdated core weighs more than an outdated plugin
+        let mut scan = scan_with_plugins(&["a"]);
+        scan.wordpress_version = Some("6.0.0".to_string());
+        let analysis = Analyzer::new(scan).analyze();
+        assert_eq!(analysis.security_score, 60);
+        assert_eq!(analysis.grade, 'D');
+    }
+
+    #[test]
+    fn security_score_clamps_at_zero() {
+        let slugs: Vec<String> = (0..20).map(|i| format!("plugin-{}", i)).collect();
+        let slugs: Vec<&str> = slugs.iter().map(String::as_str).collect();
+        let analysis = Analyzer::new(scan_with_plugins(&slugs)).analyze();
+        assert_eq!(analysis.security_score, 0);
+        assert_eq!(analysis.grade, 'F');
+    }
+}
@@ -0,0 +1,248 @@
+//! Comparison of two analyses of the same site
+
+use crate::analyze::{Analysis, ComponentAnalysis, ComponentStatus, ComponentType};
+use serde::{Deserialize, Serialize};
+
+/// A component present in both analyses whose version or status changed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentChange {
+    /// Component type
+    pub component_type: ComponentType,
+
+    /// Component name/slug
+    pub name: String,
+
+    /// Version in the baseline analysis
+    pub old_version: String,
+
+    /// Version in the new analysis
+    pub new_version: String,
+
+    /// Status in the baseline analysis
+    pub old_status: ComponentStatus,
+
+    /// Status in the new analysis
+    pub new_status: ComponentStatus,
+}
+
+impl ComponentChange {
+    /// Check if the detected version changed
+    pub fn version_changed(&self) -> bool {
+        self.old_version != self.new_version
+    }
+
+    /// Check if the status changed (e.g., Ok to Outdated)
+    pub fn status_changed(&self) -> bool {
+        self.old_status != self.new_status
+    }
+
+    /// Check if the component went from any other status to Outdated
+    pub fn became_outdated(&self) -> bool {
+        self.new_status == ComponentStatus::Outdated && self.old_status != ComponentStatus::Outdated
+    }
+}
+
+/// Differences between a baseline analysis and a newer one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisDiff {
+    /// Target URL of the new analysis
+    pub url: String,
+
+    /// Components only present in the new analysis, ordered by type then name
+    pub added: Vec<ComponentAnalysis>,
+
+    /// Components only present in the baseline, ordered by type then name
+    pub removed: Vec<ComponentAnalysis>,
+
+    /// Components present in both whose version or status changed
+    pub changed: Vec<ComponentChange>,
+}
+
+impl AnalysisDiff {
+    /// Check if nothing changed between the two analyses
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Count components that are newly outdated (added as outdated or became outdated)
+    pub fn newly_outdated_count(&self) -> usize {
+        let added = self.added.iter().filter(|c| c.is_outdated()).count();
+        let changed = self.changed.iter().filter(|c| c.became_outdated()).count();
+        added + changed
+    }
+
+    /// Record a change if the version or status of a component differs
+    fn compare(&mut self, old: &ComponentAnalysis, new: &ComponentAnalysis) {
+        if old.version != new.version || old.status != new.status {
+            self.changed.push(ComponentChange {
+                component_type: new.component_type,
+                name: new.name.clone(),
+                old_version: old.version.clone(),
+                new_version: new.version.clone(),
+                old_status: old.status,
+                new_status: new.status,
+            });
+        }
+    }
+
+    /// Compare a single component that is matched by name
+    ///
+    /// A renamed component (e.g., a switched theme or server) is recorded as
+    /// the old one removed and the new one added.
+    fn compare_named(&mut self, old: Option<&ComponentAnalysis>, new: Option<&ComponentAnalysis>) {
+        let old = old.filter(|c| c.is_detected());
+        let new = new.filter(|c| c.is_detected());
+        match (old, new) {
+            (Some(old), Some(new)) if old.name == new.name => self.compare(old, new),
+            (old, new) => {
+                self.removed.extend(old.cloned());
+                self.added.extend(new.cloned());
+            }
+        }
+    }
+}
+
+/// Compare a baseline analysis against a newer analysis of the same site
+///
+/// Core, theme, page builder, PHP and server are compared by name: a switched
+/// theme is reported as the old theme removed and the new one added.
+/// Components that were not detected are ignored, so a theme that stopped
+/// being detected shows up as removed.
+pub fn diff(old: &Analysis, new: &Analysis) -> AnalysisDiff {
+    let mut result = AnalysisDiff {
+        url: new.url.clone(),
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+    };
+
+    result.compare_named(Some(&old.wordpress), Some(&new.wordpress));
+    result.compare_named(Some(&old.theme), Some(&new.theme));
+
+    for (slug, old_plugin) in &old.plugins {
+        match new.plugins.get(slug) {
+            Some(new_plugin) => result.compare(old_plugin, new_plugin),
+            None => result.removed.push(old_plugin.clone()),
+        }
+    }
+    result.added.extend(
+        new.plugins
+            .iter()
+            .filter(|(slug, _)| !old.plugins.contains_key(*slug))
+            .map(|(_, plugin)| plugin.clone()),
+    );
+
+    result.compare_named(old.page_builder.as_ref(), new.page_builder.as_ref());
+    result.compare_named(old.php.as_ref(), new.php.as_ref());
+    result.compare_named(old.server.as_ref(), new.server.as_ref());
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyze::Analyzer;
+    use crate::scanner::{PageBuilderInfo, PluginInfo, ScanResult, ThemeInfo};
+    use url::Url;
+
+    fn analysis(core: &str, theme: Option<&str>, plugins: &[(&str, &str)]) -> Analysis {
+        let scan = ScanResult {
+            wordpress_detected: true,
+            wordpress_version: Some(core.to_string()),
+            wordpress_latest: Some("6.4.2".to_string()),
+            theme: theme.map(|slug| ThemeInfo {
+                slug: slug.to_string(),
+                version: Some("1.0.0".to_string()),
+                latest_version: Some("1.0.0".to_string()),
+                disputed_version: None,
+                last_updated: None,
+                tested_up_to: None,
+            }),
+            plugins: plugins
+                .iter()
+                .map(|(slug, version)| PluginInfo {
+                    slug: slug.to_string(),
+                    version: Some(version.to_string()),
+                    latest_version: Some("2.0.0".to_string()),
+                    active_installs: None,
+                    listed: None,
+                    last_updated: None,
+                    tested_up_to: None,
+                })
+                .collect(),
+            ..ScanResult::empty(Url::parse("https://example.com/").unwrap())
+        };
+        Analyzer::new(scan).analyze()
+    }
+
+    fn runtime(php: &str, server: &str, builder: Option<&str>) -> Analysis {
+        let scan = ScanResult {
+            wordpress_detected: true,
+            php_version: Some(php.to_string()),
+            server: Some(server.to_string()),
+            page_builder: builder.map(|name| PageBuilderInfo {
+                name: name.to_string(),
+                version: None,
+            }),
+            ..ScanResult::empty(Url::parse("https://example.com/").unwrap())
+        };
+        Analyzer::new(scan).analyze()
+    }
+
+    #[test]
+    fn identical_analyses_have_no_diff() {
+        let old = analysis("6.4.2", Some("astra"), &[("akismet", "2.0.0")]);
+        let new = analysis("6.4.2", Some("astra"), &[("akismet", "2.0.0")]);
+        assert!(diff(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn reports_added_removed_and_changed() {
+        let old = analysis(
+            "6.4.2",
+            Some("astra"),
+            &[("akismet", "2.0.0"), ("hello-dolly", "1.0.0")],
+        );
+        let new = analysis(
+            "6.4.1",
+            Some("kadence"),
+            &[("akismet", "1.9.0"), ("wpforms", "2.0.0")],
+        );
+        let result = diff(&old, &new);
+
+        let added: Vec<&str> = result.added.iter().map(|c| c.name.as_str()).collect();
+        let removed: Vec<&str> = result.removed.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(added, vec!["kadence", "wpforms"]);
+        assert_eq!(removed, vec!["astra", "hello-dolly"]);
+
+        let changed: Vec<&str> = result.changed.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(changed, vec!["WordPress", "akismet"]);
+        let akismet = &result.changed[1];
+        assert!(akismet.version_changed());
+        assert_eq!(akismet.old_status, ComponentStatus::Ok);
+        assert_eq!(akismet.new_status, ComponentStatus::Outdated);
+        assert!(akismet.became_outdated());
+
+        assert_eq!(result.newly_outdated_count(), 2);
+    }
+
+    #[test]
+    fn reports_runtime_and_builder_changes() {
+        let old = runtime("8.3.4", "Apache/2.4.58", None);
+        let new = runtime("7.4.33", "nginx/1.26.0", Some("Elementor"));
+        let result = diff(&old, &new);
+
+        let added: Vec<&str> = result.added.iter().map(|c| c.name.as_str()).collect();
+        let removed: Vec<&str> = result.removed.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(added, vec!["Elementor", "nginx"]);
+        assert_eq!(removed, vec!["Apache"]);
+
+        assert_eq!(result.changed.len(), 1);
+        let php = &result.changed[0];
+        assert_eq!(php.component_type, ComponentType::Php);
+        assert_eq!(php.old_version, "8.3.4");
+        assert_eq!(php.new_version, "7.4.33");
+        assert!(php.became_outdated());
+    }
+}
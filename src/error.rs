@@ -16,6 +16,10 @@ pub enum Error {
     #[error("failed to create HTTP client: {0}")]
     HttpClient(String),
 
+    /// Builder options contradict each other
+    #[error("conflicting scanner options: {0}")]
+    ConflictingOptions(String),
+
     /// HTTP request failed
     #[error("HTTP request failed: {0}")]
     HttpRequest(String),
@@ -24,12 +28,30 @@ pub enum Error {
     #[error("HTTP error: status {0}")]
     HttpStatus(u16),
 
+    /// Scan did not finish within the configured total timeout
+    #[error("scan exceeded total timeout of {0:?}")]
+    ScanTimeout(std::time::Duration),
+
+    /// Response body exceeded the configured size limit
+    #[error("response body exceeds {0} bytes")]
+    BodyTooLarge(usize),
+
+    /// Scan used up its request budget, so the request was not sent
+    #[error("request budget of {0} exhausted")]
+    RequestBudgetExceeded(usize),
+
+    /// WordPress.org API kept answering 429 Too Many Requests
+    #[error("WordPress.org API rate limit exceeded")]
+    ApiRateLimited,
+
     /// Site does not appear to be WordPress
     #[error("site does not appear to be WordPress")]
     NotWordPress,
 
     /// Invalid output format specified
-    #[error("invalid output format: '{0}' (valid: human, json, none)")]
+    #[error(
+        "invalid output format: '{0}' (valid: human, json, jsonl, html, nagios, summary, none)"
+    )]
     InvalidOutputFormat(String),
 
     /// Invalid output detail level specified
@@ -37,9 +59,13 @@ pub enum Error {
     InvalidOutputDetail(String),
 
     /// Invalid output sort order specified
-    #[error("invalid output sort: '{0}' (valid: status, name)")]
+    #[error("invalid output sort: '{0}' (valid: type, name, status, installs)")]
     InvalidOutputSort(String),
 
+    /// Reading an input file failed
+    #[error("failed to read input file: {0}")]
+    InputFailed(#[source] std::io::Error),
+
     /// Output operation failed
     #[error("output failed: {0}")]
     OutputFailed(#[source] std::io::Error),
@@ -47,4 +73,8 @@ pub enum Error {
     /// JSON serialization failed
     #[error("JSON serialization failed")]
     SerializationFailed(#[from] serde_json::Error),
+
+    /// JSON deserialization failed
+    #[error("JSON deserialization failed: {0}")]
+    DeserializationFailed(#[source] serde_json::Error),
 }
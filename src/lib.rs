@@ -16,13 +16,35 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! # Features
+//!
+//! - `output` (default): the [`output`] module with human, JSON, HTML, and
+//!   Nagios formatting. Disable default features to embed only the scanner
+//!   and analyzer without the table dependencies.
+//! - `cli` (default): the `wordpress-audit` binary; implies `output`.
 
 pub mod analyze;
+pub mod diff;
 pub mod error;
+#[cfg(feature = "output")]
 pub mod output;
 pub mod scanner;
 
-pub use analyze::{Analysis, Analyzer, ComponentAnalysis, ComponentStatus};
+pub use analyze::{
+    Analysis, Analyzer, ComponentAnalysis, ComponentSource, ComponentStatus, FailOn, Note,
+    NoteSeverity, Severity,
+};
+pub use diff::{AnalysisDiff, ComponentChange, diff};
 pub use error::{Error, Result};
-pub use output::{OutputConfig, OutputFormat, OutputSort, output_analysis};
-pub use scanner::{PluginInfo, ScanResult, Scanner, ScannerBuilder, ThemeInfo};
+#[cfg(feature = "output")]
+pub use output::{
+    CheckStatus, ComponentRenderer, ComponentRow, OutputConfig, OutputFormat, OutputSort,
+    SCHEMA_VERSION, output_analyses, output_analysis, output_analysis_with_status, output_diff,
+    render_components,
+};
+pub use scanner::{
+    Confidence, DetectionMethod, DetectionPaths, Evidence, PageBuilderInfo, PluginInfo,
+    ProbeResult, RestApiStatus, ScanProgress, ScanResult, Scanner, ScannerBuilder, ThemeInfo,
+    WooCommerceInfo,
+};
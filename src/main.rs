@@ -1,32 +1,119 @@
 //! WordPress Audit CLI - Scan WordPress websites for security information
 
 use clap::{Parser, ValueEnum};
+use std::fs::File;
+use std::io::{BufWriter, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
+use std::time::Duration;
 
 use wordpress_audit::{
-    Analyzer, Scanner,
-    output::{OutputConfig, OutputFormat, OutputSort, output_analysis},
+    Analysis, Analyzer, CheckStatus, Error, FailOn, Scanner, ScannerBuilder, Severity, diff,
+    output::{
+        OutputConfig, OutputFormat, OutputSort, output_analyses, output_analysis_with_status,
+        output_diff,
+    },
 };
 
+/// Exit code when a site could not be scanned (or another error occurred)
+const ERROR_EXIT_CODE: u8 = 3;
+
 /// WordPress security scanner - detects versions, plugins, and themes
 #[derive(Parser, Debug)]
 #[command(name = "wordpress-audit")]
 #[command(version, about, long_about = None)]
+#[command(after_help = "Exit codes:
+  0  no findings at the --fail-on level
+  1  warning findings (--fail-on warning)
+  2  critical findings (--fail-on warning or critical)
+  3  a site could not be scanned, or another error
+
+With --input-file, critical findings on any site outrank a failed site.
+Nagios output uses 0 OK, 1 WARNING, 2 CRITICAL, 3 UNKNOWN instead.")]
 struct Args {
     /// URL of the WordPress site to scan
-    url: String,
+    #[arg(required_unless_present = "input_file")]
+    url: Option<String>,
+
+    /// File with one URL per line to scan (blank lines and # comments are skipped)
+    #[arg(short = 'i', long = "input-file", conflicts_with = "url")]
+    input_file: Option<PathBuf>,
+
+    /// Prior JSON scan to compare against; only changes since then are output
+    #[arg(long = "baseline", conflicts_with = "input_file")]
+    baseline: Option<PathBuf>,
 
     /// Output format
     #[arg(short = 'o', long = "output", default_value = "human", value_enum)]
     output_format: OutputFormatArg,
 
+    /// Write results to this file instead of stdout
+    #[arg(long = "output-file")]
+    output_file: Option<PathBuf>,
+
     /// Sort order for output
     #[arg(long = "sort", default_value = "type", value_enum)]
     sort: OutputSortArg,
 
+    /// Show how each detection was made (always included in JSON output)
+    #[arg(short = 'v', long = "verbose")]
+    verbose: bool,
+
+    /// Disable colored output (also off when stdout is not a terminal or NO_COLOR is set)
+    #[arg(long = "no-color")]
+    no_color: bool,
+
+    /// Leave "Not Found" rows out of tables and say what is missing below instead
+    #[arg(long = "hide-not-detected")]
+    hide_not_detected: bool,
+
+    /// Draw tables with plain ASCII instead of box-drawing characters (implies --no-color)
+    #[arg(long = "ascii")]
+    ascii: bool,
+
     /// Allow scanning private/internal IP addresses (localhost, 192.168.x.x, etc.)
     #[arg(long = "allow-private")]
     allow_private: bool,
+
+    /// Accept invalid TLS certificates from the target site (self-signed, expired)
+    #[arg(short = 'k', long = "insecure")]
+    insecure: bool,
+
+    /// Talk HTTP/1.1 only to the target site (for servers or WAFs that misbehave over HTTP/2)
+    #[arg(long = "http1")]
+    http1: bool,
+
+    /// Proxy for requests to the target site (e.g., socks5h://127.0.0.1:9050 for Tor)
+    #[arg(long = "proxy")]
+    proxy: Option<String>,
+
+    /// Probe wp-admin/theme-editor.php for disabled file editing (may trip WAFs)
+    #[arg(long = "file-edit-probe")]
+    file_edit_probe: bool,
+
+    /// Detect only the WordPress core version (fast; no theme, plugin, or exposure checks)
+    #[arg(long = "version-only")]
+    version_only: bool,
+
+    /// Stop sending requests to a site after this many (a guardrail for untrusted targets)
+    #[arg(long = "max-requests")]
+    max_requests: Option<usize>,
+
+    /// Retries of a target page rate limited with 429 and a Retry-After of up to 30s; other failures are not retried
+    #[arg(long = "max-retries", visible_alias = "retries", default_value_t = 2)]
+    max_retries: u32,
+
+    /// Timeout of each request in seconds
+    #[arg(long = "timeout", default_value_t = 30, value_parser = clap::value_parser!(u64).range(1..))]
+    timeout: u64,
+
+    /// Skip WordPress.org version lookups (no outdated detection)
+    #[arg(long = "offline")]
+    offline: bool,
+
+    /// Exit non-zero on findings: 1 for warnings, 2 for critical (ignored for nagios output; errors exit 3)
+    #[arg(long = "fail-on", default_value = "never", value_enum)]
+    fail_on: FailOnArg,
 }
 
 /// Output format argument
@@ -34,6 +121,12 @@ struct Args {
 enum OutputFormatArg {
     Human,
     Json,
+    Jsonl,
+    Html,
+    /// Nagios/Icinga check line; exit code 0 OK, 1 WARNING, 2 CRITICAL, 3 UNKNOWN
+    Nagios,
+    /// Compact block with URL, WordPress status, counts, and grade
+    Summary,
     None,
 }
 
@@ -42,6 +135,10 @@ impl From<OutputFormatArg> for OutputFormat {
         match arg {
             OutputFormatArg::Human => OutputFormat::Human,
             OutputFormatArg::Json => OutputFormat::Json,
+            OutputFormatArg::Jsonl => OutputFormat::Jsonl,
+            OutputFormatArg::Html => OutputFormat::Html,
+            OutputFormatArg::Nagios => OutputFormat::Nagios,
+            OutputFormatArg::Summary => OutputFormat::Summary,
             OutputFormatArg::None => OutputFormat::None,
         }
     }
@@ -50,12 +147,14 @@ impl From<OutputFormatArg> for OutputFormat {
 /// Output sort argument
 #[derive(Clone, Copy, Debug, ValueEnum)]
 enum OutputSortArg {
-    /// Sort by type (Core, Theme, Plugin), then by name (default)
+    /// Sort by type (Core, Theme, Builder, Plugin, PHP, Server), then by name (default)
     Type,
     /// Sort alphabetically by name only
     Name,
     /// Sort by status, then by type, then by name
     Status,
+    /// Sort by WordPress.org active installs (most first), with an Installs column
+    Installs,
 }
 
 impl From<OutputSortArg> for OutputSort {
@@ -64,44 +163,250 @@ impl From<OutputSortArg> for OutputSort {
             OutputSortArg::Type => OutputSort::Type,
             OutputSortArg::Name => OutputSort::Name,
             OutputSortArg::Status => OutputSort::Status,
+            OutputSortArg::Installs => OutputSort::Installs,
+        }
+    }
+}
+
+/// CI gate argument
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum FailOnArg {
+    /// Always exit 0 after a successful scan (default)
+    Never,
+    /// Fail on outdated components, unknown versions, or critical findings
+    Warning,
+    /// Fail only on end-of-life software or high-severity exposures
+    Critical,
+}
+
+impl From<FailOnArg> for FailOn {
+    fn from(arg: FailOnArg) -> Self {
+        match arg {
+            FailOnArg::Never => FailOn::Never,
+            FailOnArg::Warning => FailOn::Warning,
+            FailOnArg::Critical => FailOn::Critical,
         }
     }
 }
 
 #[tokio::main]
 async fn main() -> ExitCode {
-    let args = Args::parse();
+    // Usage errors exit 3 rather than clap's 2, which means critical findings here
+    let args = match Args::try_parse() {
+        Ok(args) => args,
+        Err(e) => {
+            let _ = e.print();
+            return if e.use_stderr() {
+                ExitCode::from(ERROR_EXIT_CODE)
+            } else {
+                ExitCode::SUCCESS
+            };
+        }
+    };
 
-    // Print banner for human output
-    if matches!(args.output_format, OutputFormatArg::Human) {
+    // Print banner for human output, but never into a report file
+    if matches!(args.output_format, OutputFormatArg::Human) && args.output_file.is_none() {
         print_banner();
     }
 
-    let output_config = OutputConfig::new(args.output_format.into(), args.sort.into());
+    let color = !args.no_color
+        && args.output_file.is_none()
+        && std::env::var_os("NO_COLOR").is_none()
+        && std::io::stdout().is_terminal();
+    let output_config = OutputConfig::new(args.output_format.into(), args.sort.into())
+        .verbose(args.verbose)
+        .color(color)
+        .ascii(args.ascii)
+        .show_not_detected(!args.hide_not_detected);
+
+    let output_file = args.output_file.as_deref();
+    let fail_on = args.fail_on.into();
+    let result = match (&args.url, &args.input_file) {
+        (_, Some(path)) => {
+            run_scan_many(
+                path,
+                scanner_config("", &args),
+                output_file,
+                &output_config,
+                fail_on,
+            )
+            .await
+        }
+        (Some(url), None) => {
+            run_scan(
+                scanner_config(url, &args),
+                args.baseline.as_deref(),
+                output_file,
+                &output_config,
+                fail_on,
+            )
+            .await
+        }
+        (None, None) => unreachable!("clap requires a URL or an input file"),
+    };
 
-    match run_scan(&args.url, args.allow_private, &output_config).await {
-        Ok(_) => ExitCode::SUCCESS,
+    match result {
+        Ok(code) => code,
+        Err(e) if output_config.format == OutputFormat::Nagios => {
+            println!("WORDPRESS {} - {}", CheckStatus::Unknown, e);
+            ExitCode::from(CheckStatus::Unknown.exit_code())
+        }
         Err(e) => {
             eprintln!("Error: {}", e);
-            ExitCode::FAILURE
+            ExitCode::from(ERROR_EXIT_CODE)
         }
     }
 }
 
+/// Scanner options shared by single and multi-site scans
+fn scanner_config(url: &str, args: &Args) -> ScannerBuilder {
+    let mut config = Scanner::builder(url)
+        .allow_private(args.allow_private)
+        .danger_accept_invalid_certs(args.insecure)
+        .http1_only(args.http1)
+        .file_edit_probe(args.file_edit_probe)
+        .version_only(args.version_only)
+        .max_retries(args.max_retries)
+        .timeout(Duration::from_secs(args.timeout))
+        .offline(args.offline);
+    if let Some(proxy) = &args.proxy {
+        config = config.proxy(proxy);
+    }
+    if let Some(max) = args.max_requests {
+        config = config.max_requests(max);
+    }
+    config
+}
+
 async fn run_scan(
-    url: &str,
-    allow_private: bool,
+    config: ScannerBuilder,
+    baseline: Option<&Path>,
+    output_file: Option<&Path>,
     output_config: &OutputConfig,
-) -> wordpress_audit::Result<()> {
-    let scanner = Scanner::builder(url).allow_private(allow_private).build()?;
+    fail_on: FailOn,
+) -> wordpress_audit::Result<ExitCode> {
+    // Load the baseline before scanning so a bad file fails fast
+    let baseline = baseline
+        .map(|path| {
+            let contents = std::fs::read_to_string(path).map_err(Error::InputFailed)?;
+            Analysis::from_json(&contents)
+        })
+        .transpose()?;
+
+    let scanner = config.build()?;
     let scan_result = scanner.scan().await?;
     let analysis = Analyzer::new(scan_result).analyze();
 
-    let stdout = std::io::stdout();
-    let mut writer = stdout.lock();
-    output_analysis(&analysis, output_config, &mut writer)?;
+    let mut writer = output_writer(output_file)?;
+    let (status, severity) = match baseline {
+        Some(baseline) => {
+            let diff = diff(&baseline, &analysis);
+            output_diff(&diff, output_config, &mut writer)?;
+            (
+                CheckStatus::for_diff(&diff),
+                analysis.exit_severity(fail_on),
+            )
+        }
+        None => {
+            let severity =
+                output_analysis_with_status(&analysis, output_config, fail_on, &mut writer)?;
+            (CheckStatus::for_analysis(&analysis), severity)
+        }
+    };
+    writer.flush().map_err(Error::OutputFailed)?;
+
+    Ok(exit_code(status, severity, output_config))
+}
+
+async fn run_scan_many(
+    path: &Path,
+    config: ScannerBuilder,
+    output_file: Option<&Path>,
+    output_config: &OutputConfig,
+    fail_on: FailOn,
+) -> wordpress_audit::Result<ExitCode> {
+    let contents = std::fs::read_to_string(path).map_err(Error::InputFailed)?;
+    let urls = parse_url_list(&contents);
+
+    let mut analyses = Vec::new();
+    let mut failed = false;
+    for (url, result) in Scanner::scan_many(&urls, &config).await {
+        match result {
+            Ok(scan_result) => analyses.push(Analyzer::new(scan_result).analyze()),
+            Err(e) => {
+                eprintln!("Error: {}: {}", url, e);
+                failed = true;
+            }
+        }
+    }
+
+    let mut writer = output_writer(output_file)?;
+    output_analyses(&analyses, output_config, &mut writer)?;
+    writer.flush().map_err(Error::OutputFailed)?;
+
+    // Nagios reports the worst site, counting a failed one as UNKNOWN
+    if output_config.format == OutputFormat::Nagios {
+        let worst = analyses
+            .iter()
+            .map(CheckStatus::for_analysis)
+            .chain(failed.then_some(CheckStatus::Unknown))
+            .max()
+            .unwrap_or(CheckStatus::Unknown);
+        return Ok(ExitCode::from(worst.exit_code()));
+    }
+    let worst = analyses
+        .iter()
+        .map(|analysis| analysis.exit_severity(fail_on))
+        .max()
+        .unwrap_or(Severity::None);
+    // A site that could not be scanned must not hide critical findings on the others
+    if failed && worst < Severity::Critical {
+        return Ok(ExitCode::from(ERROR_EXIT_CODE));
+    }
+    Ok(severity_exit_code(worst))
+}
+
+/// Writer for results: the given file (created or truncated), else stdout
+///
+/// Opened only once the scan has finished, so a failed scan never clobbers an
+/// existing report.
+fn output_writer(path: Option<&Path>) -> wordpress_audit::Result<Box<dyn Write>> {
+    match path {
+        Some(path) => {
+            let file = File::create(path).map_err(Error::OutputFailed)?;
+            Ok(Box::new(BufWriter::new(file)))
+        }
+        None => Ok(Box::new(std::io::stdout().lock())),
+    }
+}
+
+/// Exit code for a finished scan: the check status for Nagios output, else
+/// the `--fail-on` severity
+fn exit_code(status: CheckStatus, severity: Severity, output_config: &OutputConfig) -> ExitCode {
+    if output_config.format == OutputFormat::Nagios {
+        ExitCode::from(status.exit_code())
+    } else {
+        severity_exit_code(severity)
+    }
+}
+
+/// Exit code for a `--fail-on` severity (0 none, 1 warning, 2 critical)
+fn severity_exit_code(severity: Severity) -> ExitCode {
+    match severity {
+        Severity::None => ExitCode::SUCCESS,
+        Severity::Warning => ExitCode::from(1),
+        Severity::Critical => ExitCode::from(2),
+    }
+}
 
-    Ok(())
+/// Parse a URL list, skipping blank lines and `#` comments
+fn parse_url_list(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
 }
 
 fn print_banner() {
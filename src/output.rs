@@ -1,12 +1,62 @@
 //! Output formatting for WordPress scan results
 
-use crate::analyze::{Analysis, ComponentAnalysis, ComponentStatus, ComponentType};
+use crate::analyze::{
+    Analysis, ComponentAnalysis, ComponentStatus, ComponentType, FailOn, NoteSeverity, Severity,
+};
+use crate::diff::AnalysisDiff;
 use crate::error::{Error, Result};
 use comfy_table::{
-    Attribute, Cell, CellAlignment, Color, ContentArrangement, Table, presets::UTF8_FULL,
+    Attribute, Cell, CellAlignment, Color, ContentArrangement, Table,
+    presets::{ASCII_FULL, UTF8_FULL},
 };
+use serde::Serialize;
 use std::io::Write;
 use std::str::FromStr;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+/// Version of the JSON output schema
+///
+/// Bumped whenever the shape of the serialized envelope or [`Analysis`]
+/// changes. Current value: 14 (added `locale`).
+pub const SCHEMA_VERSION: u32 = 14;
+
+/// Versioned wrapper around machine-readable output
+#[derive(Debug, Serialize)]
+struct Envelope<'a> {
+    /// Output schema version (see [`SCHEMA_VERSION`])
+    schema_version: u32,
+    /// When the output was generated (RFC 3339, UTC)
+    generated_at: String,
+    /// Wrapped results
+    #[serde(flatten)]
+    payload: Payload<'a>,
+}
+
+/// Results carried by an [`Envelope`]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Payload<'a> {
+    /// Analysis of a single site
+    Analysis(&'a Analysis),
+    /// Analyses of multiple sites
+    Analyses(&'a [Analysis]),
+    /// Changes since a baseline analysis
+    Diff(&'a AnalysisDiff),
+}
+
+impl<'a> Envelope<'a> {
+    fn new(payload: Payload<'a>) -> Self {
+        let generated_at = OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .unwrap_or_default();
+        Self {
+            schema_version: SCHEMA_VERSION,
+            generated_at,
+            payload,
+        }
+    }
+}
 
 /// Output format for results
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -16,6 +66,14 @@ pub enum OutputFormat {
     Human,
     /// JSON output
     Json,
+    /// JSON Lines output (one compact object per line)
+    Jsonl,
+    /// Self-contained HTML report
+    Html,
+    /// Nagios/Icinga plugin status line with perfdata
+    Nagios,
+    /// Compact block with the headline numbers only
+    Summary,
     /// No output (silent mode)
     None,
 }
@@ -27,6 +85,10 @@ impl FromStr for OutputFormat {
         match s.to_lowercase().as_str() {
             "human" => Ok(Self::Human),
             "json" => Ok(Self::Json),
+            "jsonl" => Ok(Self::Jsonl),
+            "html" => Ok(Self::Html),
+            "nagios" => Ok(Self::Nagios),
+            "summary" => Ok(Self::Summary),
             "none" => Ok(Self::None),
             _ => Err(Error::InvalidOutputFormat(s.to_string())),
         }
@@ -36,13 +98,15 @@ impl FromStr for OutputFormat {
 /// Sort order for output
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum OutputSort {
-    /// Sort by type (Core, Theme, Plugin), then by name (default)
+    /// Sort by type (Core, Theme, Builder, Plugin, PHP, Server), then by name (default)
     #[default]
     Type,
     /// Sort alphabetically by name only
     Name,
     /// Sort by status, then by type, then by name
     Status,
+    /// Sort by WordPress.org active installs (most first), with an Installs column
+    Installs,
 }
 
 impl FromStr for OutputSort {
@@ -53,24 +117,97 @@ impl FromStr for OutputSort {
             "type" => Ok(Self::Type),
             "name" => Ok(Self::Name),
             "status" => Ok(Self::Status),
+            "installs" => Ok(Self::Installs),
             _ => Err(Error::InvalidOutputSort(s.to_string())),
         }
     }
 }
 
 /// Configuration for output formatting
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct OutputConfig {
     /// Output format
     pub format: OutputFormat,
     /// Sort order
     pub sort: OutputSort,
+    /// Show detection evidence in human and HTML output (always in JSON)
+    pub verbose: bool,
+    /// Use ANSI colors and bold text in human output
+    pub color: bool,
+    /// Draw human tables with plain ASCII instead of box-drawing characters
+    pub ascii: bool,
+    /// Show "Not Found" rows in human and HTML tables
+    pub show_not_detected: bool,
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            format: OutputFormat::default(),
+            sort: OutputSort::default(),
+            verbose: false,
+            color: true,
+            ascii: false,
+            show_not_detected: true,
+        }
+    }
 }
 
 impl OutputConfig {
     /// Create a new output config
     pub fn new(format: OutputFormat, sort: OutputSort) -> Self {
-        Self { format, sort }
+        Self {
+            format,
+            sort,
+            verbose: false,
+            color: true,
+            ascii: false,
+            show_not_detected: true,
+        }
+    }
+
+    /// Use ANSI colors and bold text in human output (default: on)
+    ///
+    /// Turn off when writing to a file or a CI log, where escape sequences
+    /// show up as garbage.
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Show detection evidence in human and HTML output
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    /// Draw human tables with plain ASCII and no colors (default: off)
+    ///
+    /// For legacy terminals and log collectors that mangle the Unicode
+    /// box-drawing characters.
+    pub fn ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Show rows for components that were not found (default: on)
+    ///
+    /// When off, the "Not Found" rows (core, theme, and the `-` plugin
+    /// placeholder) are left out of the table, and a line such as
+    /// `No plugins detected` says what is missing instead.
+    pub fn show_not_detected(mut self, show: bool) -> Self {
+        self.show_not_detected = show;
+        self
+    }
+
+    /// Table preset for human output
+    fn table_preset(&self) -> &'static str {
+        if self.ascii { ASCII_FULL } else { UTF8_FULL }
+    }
+
+    /// Whether human output uses colors (never in ASCII mode)
+    fn use_color(&self) -> bool {
+        self.color && !self.ascii
     }
 }
 
@@ -83,13 +220,116 @@ pub fn output_analysis<W: Write>(
     match config.format {
         OutputFormat::Human => output_human(analysis, config, writer),
         OutputFormat::Json => output_json(analysis, writer),
+        OutputFormat::Jsonl => output_jsonl(analysis, writer),
+        OutputFormat::Html => output_html(std::slice::from_ref(analysis), config, writer),
+        OutputFormat::Nagios => output_nagios(analysis, writer),
+        OutputFormat::Summary => output_summary(analysis, writer),
+        OutputFormat::None => Ok(()),
+    }
+}
+
+/// Output the analysis results and return the severity for a CI gate
+///
+/// Same output as [`output_analysis`], plus
+/// [`Analysis::exit_severity`] for `fail_on`, so a frontend can write the
+/// report and pick its exit code in one call.
+pub fn output_analysis_with_status<W: Write>(
+    analysis: &Analysis,
+    config: &OutputConfig,
+    fail_on: FailOn,
+    writer: &mut W,
+) -> Result<Severity> {
+    output_analysis(analysis, config, writer)?;
+    Ok(analysis.exit_severity(fail_on))
+}
+
+/// Output the analysis results for multiple sites
+///
+/// JSON emits a single array, JSON Lines one line per site, HTML a single
+/// page with one section per site, Nagios one status line per site, and the
+/// human and summary formats print a separate block for each site.
+pub fn output_analyses<W: Write>(
+    analyses: &[Analysis],
+    config: &OutputConfig,
+    writer: &mut W,
+) -> Result<()> {
+    match config.format {
+        OutputFormat::Human => {
+            for (i, analysis) in analyses.iter().enumerate() {
+                if i > 0 {
+                    writeln!(writer).map_err(Error::OutputFailed)?;
+                }
+                writeln!(writer, "== {} ==", analysis.url).map_err(Error::OutputFailed)?;
+                output_human(analysis, config, writer)?;
+            }
+            Ok(())
+        }
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(
+                &mut *writer,
+                &Envelope::new(Payload::Analyses(analyses)),
+            )?;
+            writeln!(writer).map_err(Error::OutputFailed)?;
+            Ok(())
+        }
+        OutputFormat::Jsonl => analyses
+            .iter()
+            .try_for_each(|analysis| output_jsonl(analysis, writer)),
+        OutputFormat::Html => output_html(analyses, config, writer),
+        OutputFormat::Nagios => analyses
+            .iter()
+            .try_for_each(|analysis| output_nagios(analysis, writer)),
+        OutputFormat::Summary => {
+            for (i, analysis) in analyses.iter().enumerate() {
+                if i > 0 {
+                    writeln!(writer).map_err(Error::OutputFailed)?;
+                }
+                output_summary(analysis, writer)?;
+            }
+            Ok(())
+        }
+        OutputFormat::None => Ok(()),
+    }
+}
+
+/// Output the changes between a baseline analysis and a new one
+///
+/// JSON and JSON Lines wrap the diff in the same versioned envelope as an
+/// analysis, under a `diff` key.
+pub fn output_diff<W: Write>(
+    diff: &AnalysisDiff,
+    config: &OutputConfig,
+    writer: &mut W,
+) -> Result<()> {
+    match config.format {
+        OutputFormat::Human => output_diff_human(diff, config, writer),
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *writer, &Envelope::new(Payload::Diff(diff)))?;
+            writeln!(writer).map_err(Error::OutputFailed)?;
+            Ok(())
+        }
+        OutputFormat::Jsonl => {
+            serde_json::to_writer(&mut *writer, &Envelope::new(Payload::Diff(diff)))?;
+            writeln!(writer).map_err(Error::OutputFailed)?;
+            Ok(())
+        }
+        OutputFormat::Html => output_diff_html(diff, writer),
+        OutputFormat::Nagios => output_diff_nagios(diff, writer),
+        OutputFormat::Summary => output_diff_summary(diff, writer),
         OutputFormat::None => Ok(()),
     }
 }
 
 /// Output JSON format
 fn output_json<W: Write>(analysis: &Analysis, writer: &mut W) -> Result<()> {
-    serde_json::to_writer_pretty(&mut *writer, analysis)?;
+    serde_json::to_writer_pretty(&mut *writer, &Envelope::new(Payload::Analysis(analysis)))?;
+    writeln!(writer).map_err(Error::OutputFailed)?;
+    Ok(())
+}
+
+/// Output JSON Lines format (single compact line)
+fn output_jsonl<W: Write>(analysis: &Analysis, writer: &mut W) -> Result<()> {
+    serde_json::to_writer(&mut *writer, &Envelope::new(Payload::Analysis(analysis)))?;
     writeln!(writer).map_err(Error::OutputFailed)?;
     Ok(())
 }
@@ -100,51 +340,158 @@ fn output_human<W: Write>(
     config: &OutputConfig,
     writer: &mut W,
 ) -> Result<()> {
-    let mut table = Table::new();
-    table
-        .load_preset(UTF8_FULL)
-        .set_content_arrangement(ContentArrangement::Dynamic)
-        .set_header(vec![
-            Cell::new("Type").add_attribute(Attribute::Bold),
-            Cell::new("Name").add_attribute(Attribute::Bold),
-            Cell::new("Version").add_attribute(Attribute::Bold),
-            Cell::new("Latest").add_attribute(Attribute::Bold),
-            Cell::new("Status").add_attribute(Attribute::Bold),
-        ]);
+    let mut table = TerminalTable::new(config);
+    render_components(analysis, config, &mut table);
+
+    writeln!(writer, "{}", table.table).map_err(Error::OutputFailed)?;
+    for line in hidden_components(analysis, config) {
+        writeln!(writer, "{}", line).map_err(Error::OutputFailed)?;
+    }
+    writeln!(writer, "Detection confidence: {}", analysis.confidence)
+        .map_err(Error::OutputFailed)?;
+
+    // Security notes below the table
+    if !analysis.notes.is_empty() {
+        writeln!(writer).map_err(Error::OutputFailed)?;
+        writeln!(writer, "Notes:").map_err(Error::OutputFailed)?;
+        for note in &analysis.notes {
+            writeln!(
+                writer,
+                "  [{}] {}",
+                note.severity,
+                sanitize_text(&note.message)
+            )
+            .map_err(Error::OutputFailed)?;
+        }
+    }
+
+    // Detection evidence, only on request
+    if config.verbose && !analysis.evidence.is_empty() {
+        writeln!(writer).map_err(Error::OutputFailed)?;
+        writeln!(writer, "Evidence:").map_err(Error::OutputFailed)?;
+        for evidence in &analysis.evidence {
+            writeln!(writer, "  {}", sanitize_text(&evidence.to_string()))
+                .map_err(Error::OutputFailed)?;
+        }
+    }
+
+    // REST namespaces, only on request
+    if config.verbose && !analysis.rest_namespaces.is_empty() {
+        writeln!(writer).map_err(Error::OutputFailed)?;
+        writeln!(
+            writer,
+            "REST namespaces ({}): {}",
+            analysis.rest_namespaces.len(),
+            sanitize_text(&analysis.rest_namespaces.join(", "))
+        )
+        .map_err(Error::OutputFailed)?;
+    }
+
+    Ok(())
+}
 
-    // Placeholder for when no plugins detected
-    let no_plugins = ComponentAnalysis {
+/// Output the headline numbers as a compact block
+fn output_summary<W: Write>(analysis: &Analysis, writer: &mut W) -> Result<()> {
+    let wordpress = &analysis.wordpress;
+    let core = if wordpress.is_detected() {
+        format!(
+            "{} ({})",
+            sanitize_text(&wordpress.version),
+            status_label(wordpress.status)
+        )
+    } else {
+        status_label(wordpress.status).to_string()
+    };
+
+    writeln!(writer, "URL:       {}", analysis.url).map_err(Error::OutputFailed)?;
+    writeln!(writer, "WordPress: {}", core).map_err(Error::OutputFailed)?;
+    writeln!(writer, "Plugins:   {}", analysis.plugin_count()).map_err(Error::OutputFailed)?;
+    writeln!(writer, "Outdated:  {}", analysis.outdated_count()).map_err(Error::OutputFailed)?;
+    writeln!(
+        writer,
+        "Grade:     {} ({}/100)",
+        analysis.grade, analysis.security_score
+    )
+    .map_err(Error::OutputFailed)?;
+    writeln!(writer, "Detection: {} confidence", analysis.confidence).map_err(Error::OutputFailed)
+}
+
+/// Output the headline numbers of a diff as a compact block
+fn output_diff_summary<W: Write>(diff: &AnalysisDiff, writer: &mut W) -> Result<()> {
+    writeln!(writer, "URL:            {}", diff.url).map_err(Error::OutputFailed)?;
+    writeln!(writer, "Added:          {}", diff.added.len()).map_err(Error::OutputFailed)?;
+    writeln!(writer, "Removed:        {}", diff.removed.len()).map_err(Error::OutputFailed)?;
+    writeln!(writer, "Changed:        {}", diff.changed.len()).map_err(Error::OutputFailed)?;
+    writeln!(writer, "Newly outdated: {}", diff.newly_outdated_count()).map_err(Error::OutputFailed)
+}
+
+/// Placeholder row shown when no plugins were detected
+fn no_plugins_placeholder() -> ComponentAnalysis {
+    ComponentAnalysis {
         component_type: ComponentType::Plugin,
         name: "-".to_string(),
         version: "-".to_string(),
         latest_version: "-".to_string(),
         status: ComponentStatus::NotDetected,
-    };
+        disputed: false,
+        estimated: false,
+        active_installs: None,
+        source: None,
+        last_updated: None,
+        tested_up_to: None,
+        abandoned: false,
+    }
+}
 
+/// Lines standing in for "Not Found" rows hidden by
+/// [`OutputConfig::show_not_detected`]
+fn hidden_components(analysis: &Analysis, config: &OutputConfig) -> Vec<&'static str> {
+    if config.show_not_detected {
+        return Vec::new();
+    }
+    let mut lines = Vec::new();
+    if !analysis.wordpress.is_detected() {
+        lines.push("WordPress not detected");
+    }
+    if !analysis.theme.is_detected() {
+        lines.push("No theme detected");
+    }
+    if analysis.plugins.is_empty() {
+        lines.push("No plugins detected");
+    }
+    lines
+}
+
+/// Collect all components of an analysis in the configured sort order
+fn sorted_components<'a>(
+    analysis: &'a Analysis,
+    sort: OutputSort,
+    no_plugins: &'a ComponentAnalysis,
+) -> Vec<&'a ComponentAnalysis> {
     // Collect all components
-    let mut components: Vec<&ComponentAnalysis> = Vec::new();
-    components.push(&analysis.wordpress);
-    components.push(&analysis.theme);
+    let mut components: Vec<&ComponentAnalysis> = analysis.components().collect();
     if analysis.plugins.is_empty() {
-        components.push(&no_plugins);
-    } else {
-        for component in analysis.plugins.values() {
-            components.push(component);
-        }
+        components.push(no_plugins);
     }
+    components.extend(&analysis.page_builder);
+    components.extend(&analysis.php);
+    components.extend(&analysis.server);
 
-    // Helper to get sort priority by type (Core=0, Theme=1, Plugin=2)
+    // Helper to get sort priority by type (Core=0, Theme=1, Builder=2, Plugin=3, PHP=4, Server=5)
     let type_order = |t: ComponentType| -> u8 {
         match t {
             ComponentType::Core => 0,
             ComponentType::Theme => 1,
-            ComponentType::Plugin => 2,
+            ComponentType::Builder => 2,
+            ComponentType::Plugin => 3,
+            ComponentType::Php => 4,
+            ComponentType::Server => 5,
         }
     };
 
     // Sort based on config
-    match config.sort {
-        // Default: by type (Core, Theme, Plugin), then by name
+    match sort {
+        // Default: by type (Core, Theme, Builder, Plugin, PHP, Server), then by name
         OutputSort::Type => {
             components.sort_by(|a, b| {
                 type_order(a.component_type)
@@ -156,6 +503,15 @@ fn output_human<W: Write>(
         OutputSort::Name => {
             components.sort_by(|a, b| a.name.cmp(&b.name));
         }
+        // By install count (unknown last), then type, then name
+        OutputSort::Installs => {
+            components.sort_by(|a, b| {
+                b.active_installs
+                    .cmp(&a.active_installs)
+                    .then_with(|| type_order(a.component_type).cmp(&type_order(b.component_type)))
+                    .then_with(|| a.name.cmp(&b.name))
+            });
+        }
         // By status first, then type, then name
         OutputSort::Status => {
             components.sort_by(|a, b| {
@@ -167,36 +523,981 @@ fn output_human<W: Write>(
         }
     }
 
-    // Add rows
-    for component in components {
-        add_component_row(&mut table, component);
+    components
+}
+
+/// Content of one component table row, independent of how it is drawn
+///
+/// Text fields are already sanitized with control characters stripped, but
+/// not escaped for any particular markup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentRow {
+    /// Component type (e.g., `Plugin`)
+    pub component_type: String,
+    /// Component name or slug
+    pub name: String,
+    /// Detected version, annotated when disputed or estimated
+    pub version: String,
+    /// Latest version, or `-`
+    pub latest: String,
+    /// Component status
+    pub status: ComponentStatus,
+    /// Formatted active installs, only when sorting by installs
+    pub installs: Option<String>,
+}
+
+impl ComponentRow {
+    /// Build the row content for a component
+    fn new(component: &ComponentAnalysis, installs: bool) -> Self {
+        Self {
+            component_type: component.component_type.to_string(),
+            name: sanitize_text(&component.name),
+            version: sanitize_text(&component.display_version()),
+            latest: sanitize_text(&component.display_latest_version()),
+            status: component.status,
+            installs: installs.then(|| format_installs(component.active_installs)),
+        }
+    }
+
+    /// Display label for the status (e.g., `Not Found`)
+    pub fn status_label(&self) -> &'static str {
+        status_label(self.status)
     }
+}
+
+/// Draws the component table of an analysis
+///
+/// [`render_components`] decides the columns, the row order, and the content
+/// of each row; a renderer only decides how they look. The built-in human
+/// and HTML formats are renderers too, so a custom one (e.g., emitting
+/// colored spans for another toolkit) sees exactly the same rows.
+pub trait ComponentRenderer {
+    /// Called once with the column titles before any row
+    fn header(&mut self, columns: &[&str]);
 
-    writeln!(writer, "{}", table).map_err(Error::OutputFailed)
+    /// Called once per component, in the configured sort order
+    fn row(&mut self, row: &ComponentRow);
 }
 
-/// Add a row for a component to the table
-fn add_component_row(table: &mut Table, component: &ComponentAnalysis) {
-    let status_cell = match component.status {
-        ComponentStatus::Ok => Cell::new("Ok")
-            .fg(Color::Green)
-            .set_alignment(CellAlignment::Center),
-        ComponentStatus::Outdated => Cell::new("Outdated")
-            .fg(Color::Yellow)
-            .set_alignment(CellAlignment::Center),
-        ComponentStatus::Unknown => Cell::new("Unknown")
-            .fg(Color::DarkGrey)
-            .set_alignment(CellAlignment::Center),
-        ComponentStatus::NotDetected => Cell::new("Not Found")
-            .fg(Color::DarkGrey)
-            .set_alignment(CellAlignment::Center),
+/// Feed the component table of an analysis to a renderer
+///
+/// Columns are Type, Name, Version, Latest, and Status, plus Installs when
+/// sorting by [`OutputSort::Installs`]. A placeholder row stands in for an
+/// empty plugin list. "Not Found" rows are skipped when
+/// [`OutputConfig::show_not_detected`] is off.
+pub fn render_components<R: ComponentRenderer + ?Sized>(
+    analysis: &Analysis,
+    config: &OutputConfig,
+    renderer: &mut R,
+) {
+    let installs = config.sort == OutputSort::Installs;
+    let mut columns = vec!["Type", "Name", "Version", "Latest", "Status"];
+    if installs {
+        columns.push("Installs");
+    }
+    renderer.header(&columns);
+
+    let no_plugins = no_plugins_placeholder();
+    for component in sorted_components(analysis, config.sort, &no_plugins) {
+        if config.show_not_detected || component.is_detected() {
+            renderer.row(&ComponentRow::new(component, installs));
+        }
+    }
+}
+
+/// Renders components into a terminal table, colored unless disabled
+struct TerminalTable {
+    table: Table,
+    color: bool,
+}
+
+impl TerminalTable {
+    fn new(config: &OutputConfig) -> Self {
+        let mut table = Table::new();
+        table
+            .load_preset(config.table_preset())
+            .set_content_arrangement(ContentArrangement::Dynamic);
+        Self {
+            table,
+            color: config.use_color(),
+        }
+    }
+}
+
+impl ComponentRenderer for TerminalTable {
+    fn header(&mut self, columns: &[&str]) {
+        self.table
+            .set_header(columns.iter().map(|column| header_cell(column, self.color)));
+    }
+
+    fn row(&mut self, row: &ComponentRow) {
+        let mut cells = vec![
+            Cell::new(&row.component_type),
+            Cell::new(&row.name),
+            Cell::new(&row.version),
+            Cell::new(&row.latest),
+            status_cell(row.status, self.color),
+        ];
+        cells.extend(row.installs.as_deref().map(Cell::new));
+        self.table.add_row(cells);
+    }
+}
+
+/// Renders components as the head and rows of an HTML table
+struct HtmlTable<'a> {
+    html: &'a mut String,
+}
+
+impl ComponentRenderer for HtmlTable<'_> {
+    fn header(&mut self, columns: &[&str]) {
+        self.html.push_str("<thead><tr>");
+        for column in columns {
+            self.html.push_str(&format!("<th>{}</th>", column));
+        }
+        self.html.push_str("</tr></thead>\n<tbody>\n");
+    }
+
+    fn row(&mut self, row: &ComponentRow) {
+        self.html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td>{}",
+            row.component_type,
+            escape_html(&row.name),
+            escape_html(&row.version),
+            escape_html(&row.latest),
+            html_status_cell(row.status),
+        ));
+        if let Some(installs) = &row.installs {
+            self.html.push_str(&format!("<td>{}</td>", installs));
+        }
+        self.html.push_str("</tr>\n");
+    }
+}
+
+/// Inline stylesheet for the HTML report; status colors mirror the terminal table
+const HTML_STYLE: &str = "\
+body { font-family: -apple-system, BlinkMacSystemFont, \"Segoe UI\", sans-serif; margin: 2em; color: #222; }
+h1 { font-size: 1.4em; }
+.summary { margin-bottom: 1.5em; }
+.summary dt { font-weight: bold; float: left; clear: left; width: 10em; }
+.summary dd { margin-left: 10em; }
+table { border-collapse: collapse; min-width: 40em; }
+th, td { border: 1px solid #ccc; padding: 0.4em 0.8em; text-align: left; }
+th { background: #f4f4f4; }
+td.status { text-align: center; font-weight: bold; }
+.status-ok { color: #2e7d32; }
+.status-ahead { color: #1565c0; }
+.status-outdated { color: #b58900; }
+.status-unknown, .status-not-detected { color: #777; }
+.notes li, .evidence li { margin: 0.2em 0; }
+";
+
+/// Output a self-contained HTML report
+///
+/// All scanned values (URL, names, versions, notes) are escaped, since slugs
+/// and versions come from attacker-controlled markup.
+fn output_html<W: Write>(
+    analyses: &[Analysis],
+    config: &OutputConfig,
+    writer: &mut W,
+) -> Result<()> {
+    let title = match analyses {
+        [analysis] => format!("WordPress Audit: {}", escape_html(&analysis.url)),
+        _ => "WordPress Audit".to_string(),
     };
 
-    table.add_row(vec![
-        Cell::new(component.component_type.to_string()),
-        Cell::new(&component.name),
-        Cell::new(&component.version),
-        Cell::new(&component.latest_version),
-        status_cell,
-    ]);
+    let mut body = String::new();
+    for analysis in analyses {
+        render_html_section(analysis, config, &mut body);
+    }
+    write_html_document(&title, &body, writer)
+}
+
+/// Wrap rendered sections in a standalone HTML page with the inline stylesheet
+fn write_html_document<W: Write>(title: &str, body: &str, writer: &mut W) -> Result<()> {
+    let html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>\n{HTML_STYLE}</style>\n</head>\n<body>\n\
+         {body}</body>\n</html>\n"
+    );
+    writer
+        .write_all(html.as_bytes())
+        .map_err(Error::OutputFailed)
+}
+
+/// Render the summary, component table, and notes of one site
+fn render_html_section(analysis: &Analysis, config: &OutputConfig, html: &mut String) {
+    let url = escape_html(&analysis.url);
+
+    html.push_str("<section>\n");
+    html.push_str(&format!("<h1>WordPress Audit: {url}</h1>\n"));
+
+    html.push_str("<dl class=\"summary\">\n");
+    html.push_str(&format!("<dt>URL</dt><dd>{url}</dd>\n"));
+    if analysis.final_url != analysis.url {
+        html.push_str(&format!(
+            "<dt>Final URL</dt><dd>{}</dd>\n",
+            escape_html(&analysis.final_url)
+        ));
+    }
+    html.push_str(&format!(
+        "<dt>WordPress</dt><dd>{}</dd>\n",
+        if analysis.is_wordpress() {
+            "Detected"
+        } else {
+            "Not detected"
+        }
+    ));
+    html.push_str(&format!(
+        "<dt>Plugins</dt><dd>{}</dd>\n",
+        analysis.plugin_count()
+    ));
+    html.push_str(&format!(
+        "<dt>Outdated</dt><dd>{}</dd>\n",
+        analysis.outdated_count()
+    ));
+    html.push_str(&format!(
+        "<dt>Security score</dt><dd>{} ({})</dd>\n",
+        analysis.security_score, analysis.grade
+    ));
+    html.push_str(&format!(
+        "<dt>Detection confidence</dt><dd>{}</dd>\n",
+        analysis.confidence
+    ));
+    html.push_str("</dl>\n");
+
+    html.push_str("<table>\n");
+    render_components(analysis, config, &mut HtmlTable { html });
+    html.push_str("</tbody>\n</table>\n");
+    for line in hidden_components(analysis, config) {
+        html.push_str(&format!("<p>{}</p>\n", line));
+    }
+
+    if !analysis.notes.is_empty() {
+        html.push_str("<h2>Notes</h2>\n<ul class=\"notes\">\n");
+        for note in &analysis.notes {
+            html.push_str(&format!(
+                "<li><strong>[{}]</strong> {}</li>\n",
+                note.severity,
+                escape_html(&sanitize_text(&note.message))
+            ));
+        }
+        html.push_str("</ul>\n");
+    }
+
+    if config.verbose && !analysis.evidence.is_empty() {
+        html.push_str("<h2>Evidence</h2>\n<ul class=\"evidence\">\n");
+        for evidence in &analysis.evidence {
+            html.push_str(&format!(
+                "<li>{}</li>\n",
+                escape_html(&sanitize_text(&evidence.to_string()))
+            ));
+        }
+        html.push_str("</ul>\n");
+    }
+
+    if config.verbose && !analysis.rest_namespaces.is_empty() {
+        html.push_str(&format!(
+            "<h2>REST namespaces ({})</h2>\n<p>{}</p>\n",
+            analysis.rest_namespaces.len(),
+            escape_html(&sanitize_text(&analysis.rest_namespaces.join(", ")))
+        ));
+    }
+    html.push_str("</section>\n");
+}
+
+/// Display label for a component status
+fn status_label(status: ComponentStatus) -> &'static str {
+    match status {
+        ComponentStatus::Ok => "Ok",
+        ComponentStatus::Ahead => "Dev",
+        ComponentStatus::Outdated => "Outdated",
+        ComponentStatus::Unknown => "Unknown",
+        ComponentStatus::NotDetected => "Not Found",
+    }
+}
+
+/// Color-coded HTML table cell for a component status
+fn html_status_cell(status: ComponentStatus) -> String {
+    let class = match status {
+        ComponentStatus::Ok => "ok",
+        ComponentStatus::Ahead => "ahead",
+        ComponentStatus::Outdated => "outdated",
+        ComponentStatus::Unknown => "unknown",
+        ComponentStatus::NotDetected => "not-detected",
+    };
+    format!(
+        "<td class=\"status status-{class}\">{}</td>",
+        status_label(status)
+    )
+}
+
+/// Strip ANSI escape sequences and control characters from untrusted text
+///
+/// Slugs and versions are scraped from the target site, so a crafted value
+/// could otherwise move the cursor, recolor or clear the terminal, or break
+/// the layout of text reports.
+fn sanitize_text(s: &str) -> String {
+    let mut sanitized = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            match chars.next() {
+                // CSI: parameters and intermediates up to a final byte in @..=~
+                Some('[') => {
+                    for c in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+                // OSC: terminated by BEL or ST (ESC \\)
+                Some(']') => {
+                    while let Some(c) = chars.next() {
+                        if c == '\x07' {
+                            break;
+                        }
+                        if c == '\x1b' {
+                            chars.next_if_eq(&'\\');
+                            break;
+                        }
+                    }
+                }
+                // Two-character escape sequence
+                _ => {}
+            }
+        } else if !c.is_control() {
+            sanitized.push(c);
+        }
+    }
+    sanitized
+}
+
+/// Escape text for safe inclusion in HTML element content and attributes
+fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Rows of a diff table: change kind, component, old/new version, old/new status
+fn diff_rows(diff: &AnalysisDiff) -> Vec<DiffRow<'_>> {
+    let added = diff.added.iter().map(|c| DiffRow {
+        change: "Added",
+        component_type: c.component_type,
+        name: &c.name,
+        old_version: "-",
+        new_version: &c.version,
+        old_status: None,
+        new_status: Some(c.status),
+    });
+    let removed = diff.removed.iter().map(|c| DiffRow {
+        change: "Removed",
+        component_type: c.component_type,
+        name: &c.name,
+        old_version: &c.version,
+        new_version: "-",
+        old_status: Some(c.status),
+        new_status: None,
+    });
+    let changed = diff.changed.iter().map(|c| DiffRow {
+        change: "Changed",
+        component_type: c.component_type,
+        name: &c.name,
+        old_version: &c.old_version,
+        new_version: &c.new_version,
+        old_status: Some(c.old_status),
+        new_status: Some(c.new_status),
+    });
+    added.chain(removed).chain(changed).collect()
+}
+
+/// One row of a diff table
+struct DiffRow<'a> {
+    change: &'static str,
+    component_type: ComponentType,
+    name: &'a str,
+    old_version: &'a str,
+    new_version: &'a str,
+    old_status: Option<ComponentStatus>,
+    new_status: Option<ComponentStatus>,
+}
+
+/// Output a diff as a human-readable table
+fn output_diff_human<W: Write>(
+    diff: &AnalysisDiff,
+    config: &OutputConfig,
+    writer: &mut W,
+) -> Result<()> {
+    if diff.is_empty() {
+        writeln!(writer, "No changes since baseline for {}", diff.url)
+            .map_err(Error::OutputFailed)?;
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(config.table_preset())
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(
+            [
+                "Change",
+                "Type",
+                "Name",
+                "Old Version",
+                "New Version",
+                "Old Status",
+                "New Status",
+            ]
+            .map(|header| header_cell(header, config.use_color())),
+        );
+
+    let optional_status_cell = |status: Option<ComponentStatus>| {
+        status.map_or_else(
+            || Cell::new("-"),
+            |status| status_cell(status, config.use_color()),
+        )
+    };
+    for row in diff_rows(diff) {
+        table.add_row(vec![
+            Cell::new(row.change),
+            Cell::new(row.component_type.to_string()),
+            Cell::new(sanitize_text(row.name)),
+            Cell::new(sanitize_text(row.old_version)),
+            Cell::new(sanitize_text(row.new_version)),
+            optional_status_cell(row.old_status),
+            optional_status_cell(row.new_status),
+        ]);
+    }
+
+    writeln!(writer, "Changes since baseline for {}", diff.url).map_err(Error::OutputFailed)?;
+    writeln!(writer, "{}", table).map_err(Error::OutputFailed)?;
+    writeln!(writer, "Newly outdated: {}", diff.newly_outdated_count())
+        .map_err(Error::OutputFailed)?;
+    Ok(())
+}
+
+/// Output a diff as a self-contained HTML report
+fn output_diff_html<W: Write>(diff: &AnalysisDiff, writer: &mut W) -> Result<()> {
+    let url = escape_html(&diff.url);
+    let mut body = format!("<section>\n<h1>Changes since baseline: {url}</h1>\n");
+
+    if diff.is_empty() {
+        body.push_str("<p>No changes.</p>\n");
+    } else {
+        body.push_str(&format!(
+            "<dl class=\"summary\">\n<dt>Newly outdated</dt><dd>{}</dd>\n</dl>\n",
+            diff.newly_outdated_count()
+        ));
+        body.push_str("<table>\n<thead><tr><th>Change</th><th>Type</th><th>Name</th><th>Old Version</th><th>New Version</th><th>Old Status</th><th>New Status</th></tr></thead>\n<tbody>\n");
+        let optional_status_cell = |status: Option<ComponentStatus>| {
+            status.map_or_else(|| "<td>-</td>".to_string(), html_status_cell)
+        };
+        for row in diff_rows(diff) {
+            body.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td>{}{}</tr>\n",
+                row.change,
+                row.component_type,
+                escape_html(&sanitize_text(row.name)),
+                escape_html(&sanitize_text(row.old_version)),
+                escape_html(&sanitize_text(row.new_version)),
+                optional_status_cell(row.old_status),
+                optional_status_cell(row.new_status),
+            ));
+        }
+        body.push_str("</tbody>\n</table>\n");
+    }
+    body.push_str("</section>\n");
+
+    write_html_document(&format!("WordPress Audit: {url}"), &body, writer)
+}
+
+/// Monitoring check status, as used by Nagios/Icinga plugins
+///
+/// Variants are ordered by exit code, so the worst of several statuses is
+/// their maximum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CheckStatus {
+    /// Everything is up to date
+    Ok,
+    /// Outdated components or unknown versions
+    Warning,
+    /// End-of-life software or high-severity exposures
+    Critical,
+    /// Site could not be checked (scan failed or not WordPress)
+    Unknown,
+}
+
+impl CheckStatus {
+    /// Status for an analysis
+    ///
+    /// High-severity notes (e.g., end-of-life PHP, exposed `debug.log`) are
+    /// critical; outdated components and unknown versions are a warning.
+    pub fn for_analysis(analysis: &Analysis) -> Self {
+        if !analysis.is_wordpress() {
+            return Self::Unknown;
+        }
+        match analysis.exit_severity(FailOn::Warning) {
+            Severity::None => Self::Ok,
+            Severity::Warning => Self::Warning,
+            Severity::Critical => Self::Critical,
+        }
+    }
+
+    /// Status for a diff: a warning if any component became outdated
+    pub fn for_diff(diff: &AnalysisDiff) -> Self {
+        if diff.newly_outdated_count() > 0 {
+            Self::Warning
+        } else {
+            Self::Ok
+        }
+    }
+
+    /// Plugin exit code (0 OK, 1 WARNING, 2 CRITICAL, 3 UNKNOWN)
+    pub fn exit_code(self) -> u8 {
+        match self {
+            Self::Ok => 0,
+            Self::Warning => 1,
+            Self::Critical => 2,
+            Self::Unknown => 3,
+        }
+    }
+}
+
+impl std::fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ok => write!(f, "OK"),
+            Self::Warning => write!(f, "WARNING"),
+            Self::Critical => write!(f, "CRITICAL"),
+            Self::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+/// Output a Nagios/Icinga status line with perfdata
+///
+/// Format: `WORDPRESS OK - 12 components, 0 outdated | outdated=0;1;;0 ...`.
+/// Outdated and unknown counts warn at 1; the score ranges from 0 to 100.
+fn output_nagios<W: Write>(analysis: &Analysis, writer: &mut W) -> Result<()> {
+    let status = CheckStatus::for_analysis(analysis);
+    let components: Vec<&ComponentAnalysis> =
+        analysis.components().filter(|c| c.is_detected()).collect();
+    let outdated = analysis.outdated_count();
+    let unknown = components.iter().filter(|c| c.is_unknown()).count();
+
+    let mut summary = if analysis.is_wordpress() {
+        format!("{} components, {} outdated", components.len(), outdated)
+    } else {
+        "WordPress not detected".to_string()
+    };
+    let high_notes = analysis
+        .notes
+        .iter()
+        .filter(|note| note.severity >= NoteSeverity::High)
+        .count();
+    if high_notes > 0 {
+        summary.push_str(&format!(", {} high-severity notes", high_notes));
+    }
+
+    writeln!(
+        writer,
+        "WORDPRESS {} - {} | outdated={};1;;0 unknown={};1;;0 plugins={};;;0 score={};;;0;100",
+        status,
+        summary,
+        outdated,
+        unknown,
+        analysis.plugin_count(),
+        analysis.security_score
+    )
+    .map_err(Error::OutputFailed)
+}
+
+/// Output a diff as a Nagios/Icinga status line with perfdata
+fn output_diff_nagios<W: Write>(diff: &AnalysisDiff, writer: &mut W) -> Result<()> {
+    let newly_outdated = diff.newly_outdated_count();
+    writeln!(
+        writer,
+        "WORDPRESS {} - {} added, {} removed, {} changed, {} newly outdated | added={};;;0 removed={};;;0 changed={};;;0 newly_outdated={};1;;0",
+        CheckStatus::for_diff(diff),
+        diff.added.len(),
+        diff.removed.len(),
+        diff.changed.len(),
+        newly_outdated,
+        diff.added.len(),
+        diff.removed.len(),
+        diff.changed.len(),
+        newly_outdated
+    )
+    .map_err(Error::OutputFailed)
+}
+
+/// Active install count as WordPress.org shows it (e.g., `5,000,000+`), or `-`
+fn format_installs(installs: Option<u64>) -> String {
+    let Some(installs) = installs else {
+        return "-".to_string();
+    };
+    let digits = installs.to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3 + 1);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted.push('+');
+    formatted
+}
+
+/// Table header cell, bold when colors are enabled
+fn header_cell(title: &str, color: bool) -> Cell {
+    let cell = Cell::new(title);
+    if color {
+        cell.add_attribute(Attribute::Bold)
+    } else {
+        cell
+    }
+}
+
+/// Color-coded table cell for a component status
+fn status_cell(status: ComponentStatus, color: bool) -> Cell {
+    let cell = Cell::new(status_label(status)).set_alignment(CellAlignment::Center);
+    if !color {
+        return cell;
+    }
+    cell.fg(match status {
+        ComponentStatus::Ok => Color::Green,
+        ComponentStatus::Ahead => Color::Blue,
+        ComponentStatus::Outdated => Color::Yellow,
+        ComponentStatus::Unknown | ComponentStatus::NotDetected => Color::DarkGrey,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyze::{Analyzer, Note};
+    use crate::scanner::{PluginInfo, ScanResult};
+    use url::Url;
+
+    fn analysis_with_plugin(slug: &str, version: &str) -> Analysis {
+        let scan = ScanResult {
+            wordpress_detected: true,
+            wordpress_version: Some("6.4.2".to_string()),
+            wordpress_latest: Some("6.4.2".to_string()),
+            theme: None,
+            plugins: vec![PluginInfo {
+                slug: slug.to_string(),
+                version: Some(version.to_string()),
+                latest_version: Some("1.1.0".to_string()),
+                active_installs: None,
+                listed: None,
+                last_updated: None,
+                tested_up_to: None,
+            }],
+            ..ScanResult::empty(Url::parse("https://example.com/").unwrap())
+        };
+        Analyzer::new(scan).analyze()
+    }
+
+    fn render(analysis: &Analysis, format: OutputFormat) -> String {
+        let mut out = Vec::new();
+        let config = OutputConfig::new(format, OutputSort::Type);
+        output_analysis(analysis, &config, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn sanitize_text_strips_escape_sequences() {
+        assert_eq!(sanitize_text("akismet"), "akismet");
+        assert_eq!(sanitize_text("evil\x1b[2J\x1b[31mred\x1b[0m"), "evilred");
+        assert_eq!(sanitize_text("a\x1b]0;title\x07b"), "ab");
+        assert_eq!(sanitize_text("a\x1b]8;;http://x\x1b\\b"), "ab");
+        assert_eq!(sanitize_text("line\r\nbreak\x00\x08"), "linebreak");
+        assert_eq!(sanitize_text("trailing\x1b["), "trailing");
+    }
+
+    #[test]
+    fn human_output_strips_ansi_from_slugs() {
+        let analysis = analysis_with_plugin("evil\x1b[2J\x1b[31mplugin", "1.0\x1b[0m\x07");
+        let text = render(&analysis, OutputFormat::Human);
+
+        assert!(!text.contains('\x1b'));
+        assert!(!text.contains('\x07'));
+        assert!(text.contains("evilplugin"));
+    }
+
+    #[test]
+    fn human_output_without_color() {
+        let analysis = analysis_with_plugin("akismet", "1.0.0");
+        let config = OutputConfig::default().color(false);
+        let mut table = TerminalTable::new(&config);
+        render_components(&analysis, &config, &mut table);
+        table.table.enforce_styling();
+        assert!(!table.table.to_string().contains('\x1b'));
+
+        let config = OutputConfig::default().color(true);
+        let mut table = TerminalTable::new(&config);
+        render_components(&analysis, &config, &mut table);
+        table.table.enforce_styling();
+        assert!(table.table.to_string().contains('\x1b'));
+    }
+
+    #[test]
+    fn custom_component_renderer() {
+        #[derive(Default)]
+        struct Recorder {
+            columns: Vec<String>,
+            rows: Vec<ComponentRow>,
+        }
+        impl ComponentRenderer for Recorder {
+            fn header(&mut self, columns: &[&str]) {
+                self.columns = columns.iter().map(|c| c.to_string()).collect();
+            }
+            fn row(&mut self, row: &ComponentRow) {
+                self.rows.push(row.clone());
+            }
+        }
+
+        let analysis = analysis_with_plugin("evil\x1b[2Jplugin", "1.0.0");
+        let config = OutputConfig::new(OutputFormat::Human, OutputSort::Installs);
+        let mut recorder = Recorder::default();
+        render_components(&analysis, &config, &mut recorder);
+
+        assert_eq!(
+            recorder.columns.last().map(String::as_str),
+            Some("Installs")
+        );
+        let plugin = recorder
+            .rows
+            .iter()
+            .find(|row| row.component_type == "Plugin")
+            .unwrap();
+        assert_eq!(plugin.name, "evilplugin");
+        assert_eq!(plugin.installs.as_deref(), Some("-"));
+        assert_eq!(plugin.status_label(), status_label(plugin.status));
+    }
+
+    #[test]
+    fn hide_not_detected_rows() {
+        let mut analysis = analysis_with_plugin("akismet", "1.0.0");
+        analysis.plugins.clear();
+        analysis.theme.status = ComponentStatus::NotDetected;
+
+        let config = OutputConfig::new(OutputFormat::Human, OutputSort::Type).color(false);
+        let mut out = Vec::new();
+        output_analysis(&analysis, &config, &mut out).unwrap();
+        let shown = String::from_utf8(out).unwrap();
+        assert!(shown.contains("Not Found"));
+        assert!(!shown.contains("No plugins detected"));
+
+        let config = config.show_not_detected(false);
+        let mut out = Vec::new();
+        output_analysis(&analysis, &config, &mut out).unwrap();
+        let hidden = String::from_utf8(out).unwrap();
+        assert!(!hidden.contains("Not Found"));
+        assert!(hidden.contains("No theme detected\nNo plugins detected\n"));
+
+        let config =
+            OutputConfig::new(OutputFormat::Html, OutputSort::Type).show_not_detected(false);
+        let mut out = Vec::new();
+        output_analysis(&analysis, &config, &mut out).unwrap();
+        let html = String::from_utf8(out).unwrap();
+        assert!(!html.contains("Not Found"));
+        assert!(html.contains("<p>No plugins detected</p>"));
+    }
+
+    #[test]
+    fn php_row_sorts_after_plugins() {
+        let mut analysis = analysis_with_plugin("akismet", "1.0.0");
+        analysis.php = Some(ComponentAnalysis {
+            component_type: ComponentType::Php,
+            name: "PHP".to_string(),
+            version: "8.3.4".to_string(),
+            latest_version: "-".to_string(),
+            status: ComponentStatus::Ok,
+            disputed: false,
+            estimated: false,
+            active_installs: None,
+            source: None,
+            last_updated: None,
+            tested_up_to: None,
+            abandoned: false,
+        });
+        let no_plugins = no_plugins_placeholder();
+        let types: Vec<ComponentType> = sorted_components(&analysis, OutputSort::Type, &no_plugins)
+            .iter()
+            .map(|c| c.component_type)
+            .collect();
+        assert_eq!(types.last(), Some(&ComponentType::Php));
+        assert!(render(&analysis, OutputFormat::Human).contains("PHP"));
+    }
+
+    #[test]
+    fn sort_by_active_installs() {
+        let mut analysis = analysis_with_plugin("akismet", "1.0.0");
+        analysis.plugins.get_mut("akismet").unwrap().active_installs = Some(6_000_000);
+        assert_eq!(format_installs(Some(6_000_000)), "6,000,000+");
+        assert_eq!(format_installs(Some(900)), "900+");
+        assert_eq!(format_installs(None), "-");
+
+        let no_plugins = no_plugins_placeholder();
+        let components = sorted_components(&analysis, OutputSort::Installs, &no_plugins);
+        assert_eq!(components[0].name, "akismet");
+
+        let mut out = Vec::new();
+        let config = OutputConfig::new(OutputFormat::Human, OutputSort::Installs).color(false);
+        output_analysis(&analysis, &config, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("Installs") && text.contains("6,000,000+"));
+        assert!(!render(&analysis, OutputFormat::Human).contains("Installs"));
+    }
+
+    #[test]
+    fn ascii_table_output() {
+        let analysis = analysis_with_plugin("akismet", "1.0.0");
+        let mut out = Vec::new();
+        let config = OutputConfig::new(OutputFormat::Human, OutputSort::Type).ascii(true);
+        output_analysis(&analysis, &config, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.is_ascii());
+        assert!(text.contains("+----") && text.contains("| akismet"));
+        assert!(!text.contains('\x1b'));
+    }
+
+    #[test]
+    fn output_with_status_returns_severity() {
+        let analysis = analysis_with_plugin("akismet", "1.0.0");
+        let config = OutputConfig::new(OutputFormat::Summary, OutputSort::Type);
+        let mut out = Vec::new();
+        let severity =
+            output_analysis_with_status(&analysis, &config, FailOn::Warning, &mut out).unwrap();
+        assert_eq!(severity, Severity::Warning);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            render(&analysis, OutputFormat::Summary)
+        );
+    }
+
+    #[test]
+    fn summary_output() {
+        let analysis = analysis_with_plugin("akismet", "1.0.0");
+        let text = render(&analysis, OutputFormat::Summary);
+        assert_eq!(text.lines().count(), 6);
+        assert!(text.contains("Plugins:   1\n"));
+        assert!(text.contains("Outdated:  1\n"));
+        assert!(text.contains(&format!(
+            "Grade:     {} ({}/100)",
+            analysis.grade, analysis.security_score
+        )));
+    }
+
+    #[test]
+    fn nagios_output_and_status() {
+        let analysis = analysis_with_plugin("akismet", "1.0.0");
+        let text = render(&analysis, OutputFormat::Nagios);
+        assert_eq!(text.lines().count(), 1);
+        assert!(text.starts_with("WORDPRESS WARNING - 2 components, 1 outdated | outdated=1;1;;0"));
+        assert_eq!(CheckStatus::for_analysis(&analysis).exit_code(), 1);
+
+        let mut critical = analysis.clone();
+        critical.notes.push(Note {
+            severity: NoteSeverity::High,
+            message: "wp-content/debug.log is publicly readable".to_string(),
+        });
+        assert_eq!(CheckStatus::for_analysis(&critical), CheckStatus::Critical);
+        assert!(render(&critical, OutputFormat::Nagios).contains(", 1 high-severity notes |"));
+        assert_eq!(
+            CheckStatus::Critical.max(CheckStatus::Warning),
+            CheckStatus::Critical
+        );
+    }
+
+    #[test]
+    fn diff_output_in_all_formats() {
+        let old = analysis_with_plugin("akismet", "1.1.0");
+        let new = analysis_with_plugin("akismet", "1.0.0");
+        let diff = crate::diff::diff(&old, &new);
+
+        let render_diff = |format| {
+            let mut out = Vec::new();
+            output_diff(
+                &diff,
+                &OutputConfig::new(format, OutputSort::Type),
+                &mut out,
+            )
+            .unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render_diff(OutputFormat::Json)).unwrap();
+        assert_eq!(json["schema_version"], SCHEMA_VERSION);
+        assert_eq!(json["diff"]["changed"][0]["name"], "akismet");
+        assert_eq!(json["diff"]["changed"][0]["new_status"], "outdated");
+
+        assert_eq!(render_diff(OutputFormat::Jsonl).lines().count(), 1);
+        assert!(render_diff(OutputFormat::Human).contains("Newly outdated: 1"));
+        assert!(render_diff(OutputFormat::Html).contains("status-outdated"));
+        assert!(render_diff(OutputFormat::Nagios).starts_with("WORDPRESS WARNING - 0 added"));
+        assert!(render_diff(OutputFormat::Summary).ends_with("Newly outdated: 1\n"));
+        assert!(render_diff(OutputFormat::None).is_empty());
+    }
+
+    #[test]
+    fn evidence_shown_only_when_verbose() {
+        let mut analysis = analysis_with_plugin("akismet", "1.0.0");
+        analysis.evidence.push(crate::scanner::Evidence {
+            finding: "WordPress version 6.4.2".to_string(),
+            source: "meta[name=generator]".to_string(),
+            detail: "WordPress 6.4.2".to_string(),
+        });
+        analysis.rest_namespaces = vec!["wp/v2".to_string(), "wpforms/v1".to_string()];
+
+        let quiet = render(&analysis, OutputFormat::Human);
+        assert!(!quiet.contains("Evidence:"));
+
+        let mut out = Vec::new();
+        let config = OutputConfig::new(OutputFormat::Human, OutputSort::Type).verbose(true);
+        output_analysis(&analysis, &config, &mut out).unwrap();
+        let verbose = String::from_utf8(out).unwrap();
+        assert!(
+            verbose.contains("WordPress version 6.4.2 via meta[name=generator] (WordPress 6.4.2)")
+        );
+        assert!(verbose.contains("REST namespaces (2): wp/v2, wpforms/v1"));
+        assert!(!quiet.contains("REST namespaces"));
+
+        let json = render(&analysis, OutputFormat::Json);
+        assert!(json.contains(r#""source": "meta[name=generator]""#));
+    }
+
+    #[test]
+    fn html_output_escapes_component_values() {
+        let analysis = analysis_with_plugin("<script>alert(1)</script>", "1.0\"'&");
+        let html = render(&analysis, OutputFormat::Html);
+
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(html.contains("1.0&quot;&#39;&amp;"));
+    }
+
+    #[test]
+    fn html_output_includes_summary() {
+        let analysis = analysis_with_plugin("akismet", "1.0.0");
+        let html = render(&analysis, OutputFormat::Html);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<dt>URL</dt><dd>https://example.com/</dd>"));
+        assert!(html.contains("<dt>Outdated</dt><dd>1</dd>"));
+        assert!(html.contains("status-outdated"));
+        assert!(!html.contains("<link"));
+    }
 }
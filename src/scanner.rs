@@ -2,37 +2,306 @@
 //!
 //! Detects WordPress version, plugins, and themes by analyzing the website.
 
+use crate::analyze::{
+    Analysis, Analyzer, ComponentAnalysis, ComponentType, compare_versions, parse_api_date,
+};
 use crate::error::{Error, Result};
+use futures::stream::{self, Stream, StreamExt};
 use regex::Regex;
-use reqwest::Client;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::header::{ACCEPT_LANGUAGE, HeaderMap, HeaderValue};
+use reqwest::redirect::Policy;
+use reqwest::{Certificate, Client, Proxy};
 use scraper::{Html, Selector};
-use serde::Deserialize;
-use std::collections::HashSet;
-use std::net::{IpAddr, ToSocketAddrs};
-use std::time::Duration;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::hash::BuildHasher;
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, instrument, trace, warn};
 use url::Url;
 
 /// User agent for requests (standard Chrome on Windows)
 const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36";
 
-/// Request timeout in seconds
-const TIMEOUT_SECS: u64 = 30;
+/// Default timeout of a single request
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
 
-/// WordPress.org API base URL
+/// Default maximum response body size (5 MiB)
+const DEFAULT_MAX_BODY_BYTES: usize = 5 * 1024 * 1024;
+
+/// Default maximum number of redirects to follow
+const DEFAULT_MAX_REDIRECTS: usize = 10;
+
+/// Default WordPress.org API base URL
 const WP_API_BASE: &str = "https://api.wordpress.org";
 
-/// WordPress detection paths
-const WP_JSON_PATH: &str = "/wp-json/";
-const WP_FEED_PATH: &str = "/feed/";
-const WP_README_PATH: &str = "/readme.html";
+/// Retries of a WordPress.org API request answered with 429 Too Many Requests
+const API_RATE_LIMIT_RETRIES: u32 = 2;
+
+/// Initial backoff after a 429 without `Retry-After`; doubles on each retry
+const API_RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Longest `Retry-After` delay honored before giving up on a lookup
+const API_RETRY_AFTER_MAX: Duration = Duration::from_secs(10);
+
+/// Default retries of a target page answered with 429 and a `Retry-After`
+const DEFAULT_MAX_RETRIES: u32 = 2;
+
+/// Longest `Retry-After` delay from the target site honored before giving up
+const TARGET_RETRY_AFTER_MAX: Duration = Duration::from_secs(30);
+
+/// WordPress detection paths (relative to the install directory)
+const WP_JSON_PATH: &str = "wp-json/";
+const WP_FEED_PATH: &str = "feed/";
+const WP_README_PATH: &str = "readme.html";
+const WP_DEBUG_LOG_PATH: &str = "wp-content/debug.log";
+const WP_SIGNUP_PATH: &str = "wp-signup.php";
+const WP_LOGIN_PATH: &str = "wp-login.php";
+const WP_ADMIN_PATH: &str = "wp-admin/";
+const WP_CRON_PATH: &str = "wp-cron.php";
+const WP_THEME_EDITOR_PATH: &str = "wp-admin/theme-editor.php";
+
+/// Backup and editor swap files probed in the install directory by default
+const DEFAULT_BACKUP_FILES: &[&str] = &[
+    "wp-config.php.bak",
+    ".wp-config.php.swp",
+    "database.sql",
+    "backup.zip",
+];
+
+/// WooCommerce detection markers
+const WC_PLUGIN_SLUG: &str = "woocommerce";
+const WC_REST_NAMESPACE_PREFIX: &str = "wc/";
+const WC_STORE_API_PATH: &str = "?rest_route=/wc/store/v1/products";
+
+/// Timeout for the `wp-cron.php` probe, which can be slow when cron jobs run
+const CRON_PROBE_TIMEOUT_SECS: u64 = 5;
+
+/// Bytes to request when probing potentially large files
+const PROBE_RANGE: &str = "bytes=0-1023";
+
+/// Bytes of an asset read for a version header (matches [`PROBE_RANGE`])
+const ASSET_HEADER_BYTES: usize = 1024;
+
+/// REST API namespace prefixes registered by popular plugins, with the plugin slug
+///
+/// WooCommerce (`wc/`) is left out since [`Scanner::detect_woocommerce`]
+/// handles it separately.
+const REST_NAMESPACE_PLUGINS: &[(&str, &str)] = &[
+    ("akismet/", "akismet"),
+    ("buddypress/", "buddypress"),
+    ("contact-form-7/", "contact-form-7"),
+    ("elementor/", "elementor"),
+    ("gf/", "gravityforms"),
+    ("jetpack/", "jetpack"),
+    ("litespeed/", "litespeed-cache"),
+    ("mc4wp/", "mailchimp-for-wp"),
+    ("rankmath/", "seo-by-rank-math"),
+    ("redirection/", "redirection"),
+    ("tribe/events/", "the-events-calendar"),
+    ("wordfence/", "wordfence"),
+    ("wp-statistics/", "wp-statistics"),
+    ("wpforms/", "wpforms"),
+    ("yoast/", "wordpress-seo"),
+];
+
+/// `admin-ajax.php` action prefixes registered by popular plugins, with the plugin slug
+///
+/// Matched against `action` values in inline scripts, for plugins that wire
+/// up AJAX without loading assets from their own directory. WooCommerce is
+/// left out for the same reason as in [`REST_NAMESPACE_PLUGINS`].
+const AJAX_ACTION_PLUGINS: &[(&str, &str)] = &[
+    ("cmplz_", "complianz-gdpr"),
+    ("elementor_pro_", "elementor-pro"),
+    ("gf_", "gravityforms"),
+    ("mc4wp_", "mailchimp-for-wp"),
+    ("nf_ajax_", "ninja-forms"),
+    ("wordfence_", "wordfence"),
+    ("wpforms_", "wpforms"),
+    ("wpgdprc_", "wp-gdpr-compliance"),
+    ("yith_wcwl_", "yith-woocommerce-wishlist"),
+];
+
+/// REST namespace roots registered by WordPress core and WooCommerce
+///
+/// Never probed as plugin slugs; see [`Scanner::rest_namespace_candidates`].
+const CORE_REST_NAMESPACES: &[&str] = &[
+    "batch",
+    "oembed",
+    "wc",
+    "wc-admin",
+    "wc-analytics",
+    "wc-telemetry",
+    "wp",
+    "wp-block-editor",
+    "wp-site-health",
+];
+
+/// REST API routes added in known WordPress releases, newest first
+///
+/// A route's presence means the site runs at least that release; matched as a
+/// prefix of the route keys in the `/wp-json/` index.
+const REST_ROUTE_VERSIONS: &[(&str, &str)] = &[
+    ("/wp/v2/font-families", "6.5"),
+    ("/wp/v2/block-patterns/patterns", "6.0"),
+    ("/wp/v2/global-styles", "5.9"),
+    ("/wp/v2/navigation", "5.9"),
+    ("/wp/v2/templates", "5.8"),
+    ("/wp/v2/widgets", "5.8"),
+    ("/wp/v2/block-types", "5.5"),
+    ("/wp/v2/plugins", "5.5"),
+    ("/wp/v2/block-renderer", "5.0"),
+];
+
+/// Plugins that name themselves in JSON-LD blocks, as (name prefix, slug)
+///
+/// Matched case-insensitively against a `generator` string only. A
+/// `SoftwareApplication` name and `softwareVersion` describe whatever the page
+/// is about (a plugin's product page, say), not what the site runs.
+const JSON_LD_PLUGINS: &[(&str, &str)] = &[
+    ("All in One SEO", "all-in-one-seo-pack"),
+    ("Rank Math", "seo-by-rank-math"),
+    (
+        "Schema & Structured Data for WP",
+        "schema-and-structured-data-for-wp",
+    ),
+    ("Schema Pro", "wp-schema-pro"),
+    ("SEOPress", "wp-seopress"),
+    ("The SEO Framework", "autodescription"),
+    ("Yoast SEO", "wordpress-seo"),
+];
 
 /// WordPress cookie prefixes
 const WP_COOKIE_PREFIXES: &[&str] = &["wordpress_", "wp-"];
 const WP_LANG_COOKIE: &str = "wp_lang";
 
+/// Markup of the network signup form served by `wp-signup.php`
+const MULTISITE_SIGNUP_MARKERS: &[&str] = &["setupform", "signup_for"];
+
+/// REST namespaces registered only on multisite networks (WP Ultimo)
+const MULTISITE_REST_NAMESPACES: &[&str] = &["wu/v2"];
+
+/// WordPress REST API link relation (sent in the `Link` header)
+const WP_API_LINK_REL: &str = "https://api.w.org/";
+
+/// CDNs and proxies recognized from homepage headers: (header, value
+/// substring or "" for any value, name). More specific entries come first.
+/// A bare `X-Cache` is also sent by Varnish and other local caches, so it
+/// only counts with a provider's value.
+const CDN_SIGNATURES: &[(&str, &str, &str)] = &[
+    ("cf-ray", "", "Cloudflare"),
+    ("server", "cloudflare", "Cloudflare"),
+    ("x-amz-cf-id", "", "Amazon CloudFront"),
+    ("x-sucuri-id", "", "Sucuri"),
+    ("server", "akamaighost", "Akamai"),
+    ("x-served-by", "cache-", "Fastly"),
+    ("x-cache", "cloudfront", "Amazon CloudFront"),
+];
+
+/// WordPress XML-RPC endpoint (advertised in the `X-Pingback` header)
+const WP_XMLRPC_PATH: &str = "xmlrpc.php";
+
+/// `X-Powered-By` values emitted by WordPress and WordPress-specific hosts
+const WP_POWERED_BY_HINTS: &[&str] = &["wordpress", "wp engine", "wpengine"];
+
+/// Query parameters that carry asset versions, in order of preference
+const VERSION_PARAMS: &[&str] = &["ver", "v", "rev", "cache"];
+
+/// Default number of concurrent requests when probing paths
+const DEFAULT_CONCURRENCY: usize = 8;
+
 /// Paths to skip when detecting plugins
 const SKIP_PLUGIN_SLUGS: &[&str] = &["index", "cache"];
 
+/// Directory names that themes and page builders use for bundled modules
+/// under a nested `plugins/` path; never the slug of an installed plugin
+const BUNDLED_PLUGIN_SLUGS: &[&str] = &["assets", "includes", "lib", "modules", "vendor"];
+
+/// Core's `.maintenance` message, which alone marks a maintenance page
+const CORE_MAINTENANCE_MESSAGE: &str = "briefly unavailable for scheduled maintenance";
+
+/// Asset paths of splash page plugins, matched case-insensitively
+///
+/// Some of them (SeedProd's `coming-soon`) also serve ordinary landing pages,
+/// so an asset only counts alongside [`MAINTENANCE_PAGE_TEXT`].
+const MAINTENANCE_PLUGIN_ASSETS: &[&str] = &[
+    "/plugins/wp-maintenance-mode/",
+    "/plugins/cmp-coming-soon-maintenance/",
+    "/plugins/under-construction-page/",
+    "/plugins/coming-soon/",
+    "/plugins/maintenance/",
+];
+
+/// Wording of a splash page, matched case-insensitively
+const MAINTENANCE_PAGE_TEXT: &[&str] = &[
+    "coming soon",
+    "under construction",
+    "maintenance mode",
+    "down for maintenance",
+];
+
+/// Markers left by a page builder in the rendered page
+struct PageBuilderSignature {
+    name: &'static str,
+    /// Classes on the `<body>` element
+    body_classes: &'static [&'static str],
+    /// Prefixes of a `meta[name=generator]` value, which may carry a version
+    generators: &'static [&'static str],
+    /// Asset path fragments (matched without `wp-content`, which may be renamed)
+    asset_paths: &'static [&'static str],
+}
+
+/// Page builders worth reporting on their own, checked in order
+const PAGE_BUILDERS: &[PageBuilderSignature] = &[
+    PageBuilderSignature {
+        name: "Elementor",
+        body_classes: &["elementor-default", "elementor-page"],
+        generators: &["Elementor"],
+        asset_paths: &["/plugins/elementor/"],
+    },
+    PageBuilderSignature {
+        name: "Divi",
+        body_classes: &["et_pb_pagebuilder_layout", "et_divi_builder"],
+        generators: &["Divi"],
+        asset_paths: &["/themes/Divi/", "/plugins/divi-builder/"],
+    },
+    PageBuilderSignature {
+        name: "Beaver Builder",
+        body_classes: &["fl-builder"],
+        generators: &[],
+        asset_paths: &[
+            "/plugins/bb-plugin/",
+            "/plugins/beaver-builder-lite-version/",
+        ],
+    },
+    PageBuilderSignature {
+        name: "WPBakery",
+        body_classes: &["wpb-js-composer"],
+        generators: &[
+            "Powered by WPBakery Page Builder",
+            "Powered by Visual Composer",
+        ],
+        asset_paths: &["/plugins/js_composer/"],
+    },
+    PageBuilderSignature {
+        name: "Oxygen",
+        body_classes: &["oxygen-body"],
+        generators: &[],
+        asset_paths: &["/plugins/oxygen/"],
+    },
+    PageBuilderSignature {
+        name: "Bricks",
+        body_classes: &["bricks-is-frontend"],
+        generators: &[],
+        asset_paths: &["/themes/bricks/"],
+    },
+];
+
 /// Allowed URL schemes
 const ALLOWED_SCHEMES: &[&str] = &["http", "https"];
 
@@ -41,16 +310,252 @@ const ALLOWED_SCHEMES: &[&str] = &["http", "https"];
 pub struct ScanResult {
     /// Target URL
     pub url: Url,
+    /// URL the homepage was served from after following redirects
+    pub final_url: Url,
     /// Whether WordPress was detected (even without version)
     pub wordpress_detected: bool,
     /// WordPress version if detected
     pub wordpress_version: Option<String>,
+    /// Lower bound such as `>=6.0` estimated from REST API routes when the
+    /// exact version is hidden (None if a version was found or no route helps)
+    pub wordpress_version_estimate: Option<String>,
     /// Latest WordPress version
     pub wordpress_latest: Option<String>,
     /// Main theme if detected
     pub theme: Option<ThemeInfo>,
     /// Detected plugins
     pub plugins: Vec<PluginInfo>,
+    /// Page builder the site is built with (e.g., Elementor, Divi)
+    pub page_builder: Option<PageBuilderInfo>,
+    /// Whether `/wp-content/debug.log` is publicly readable (None if the probe failed)
+    pub debug_log_exposed: Option<bool>,
+    /// Backup files served from the install directory (e.g., `wp-config.php.bak`)
+    pub exposed_backups: Vec<String>,
+    /// WooCommerce details if the site runs a store
+    pub woocommerce: Option<WooCommerceInfo>,
+    /// Host serving `wp-content` assets when it differs from the site (e.g., a CDN)
+    pub cdn_host: Option<String>,
+    /// CDN or proxy in front of the site (e.g., `Cloudflare`), from the
+    /// homepage headers. It can mask cookies and headers WordPress sets.
+    pub cdn: Option<String>,
+    /// Custom directory replacing `wp-content` (e.g., `app`), if it was renamed
+    pub content_dir: Option<String>,
+    /// Whether the site is a multisite network (None if ambiguous)
+    pub is_multisite: Option<bool>,
+    /// Site locale in WordPress form (e.g., `de_DE`), from the `wp_lang`
+    /// cookie or the `<html lang>` attribute
+    pub locale: Option<String>,
+    /// Whether the default login page is publicly reachable (None if the probe failed)
+    pub login_accessible: Option<bool>,
+    /// Whether the login page was moved off `wp-login.php`, a hardening signal
+    /// (None if neither a homepage link nor a `wp-admin/` redirect tells)
+    pub login_relocated: Option<bool>,
+    /// Custom login URL, when it could be discovered
+    pub login_url: Option<String>,
+    /// XML-RPC endpoint advertised by the site, which may be relocated from
+    /// `xmlrpc.php` (None if not advertised)
+    pub xmlrpc_url: Option<String>,
+    /// Whether `wp-cron.php` can be triggered externally (None if the probe failed)
+    pub wp_cron_exposed: Option<bool>,
+    /// Whether `wp-admin/theme-editor.php` is blocked outright, a low-confidence
+    /// sign of disabled file editing (None if not probed or inconclusive; see
+    /// [`ScannerBuilder::file_edit_probe`])
+    pub file_editor_blocked: Option<bool>,
+    /// REST API availability (None if the request failed or was inconclusive)
+    pub rest_api_status: Option<RestApiStatus>,
+    /// Namespaces listed by the REST API root (empty if it wasn't readable)
+    pub rest_namespaces: Vec<String>,
+    /// Namespaces guessed from the route keys of the REST API root that it
+    /// doesn't list itself (e.g., `legacy-api`); heuristic, used only to
+    /// look for plugins
+    pub rest_route_namespaces: Vec<String>,
+    /// PHP version leaked by the `X-Powered-By` header (None if not exposed)
+    pub php_version: Option<String>,
+    /// Server software from the `Server` header, e.g. `nginx/1.18.0` (None if
+    /// absent or blank)
+    pub server: Option<String>,
+    /// Whether the homepage is a maintenance or "coming soon" splash page that
+    /// may hide the real site (None if the homepage could not be fetched)
+    pub maintenance_mode: Option<bool>,
+    /// Whether the homepage could not be fetched (e.g., a WAF challenge), so
+    /// results come from the other probes only
+    pub partial: bool,
+    /// Whether the [request budget](ScannerBuilder::max_requests) ran out, so
+    /// later probes and lookups were skipped
+    pub truncated: bool,
+    /// Page fetches that failed during the scan, as `path: error`
+    pub failed_probes: Vec<String>,
+    /// WordPress.org lookups abandoned because the API kept rate limiting
+    pub api_rate_limited: usize,
+    /// How each detection was made, in the order detections ran
+    pub evidence: Vec<Evidence>,
+    /// Independent detection methods that fired, for [`confidence`](Self::confidence)
+    pub detection_methods: BTreeSet<DetectionMethod>,
+}
+
+/// How a detection was made, for auditing results
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Evidence {
+    /// What was found (e.g., `WordPress version 6.4.2`)
+    pub finding: String,
+    /// Detection method (e.g., `meta[name=generator]`, `feed`, `REST API`)
+    pub source: String,
+    /// Matched snippet or probed URL
+    pub detail: String,
+}
+
+impl std::fmt::Display for Evidence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} via {} ({})", self.finding, self.source, self.detail)
+    }
+}
+
+impl ScanResult {
+    /// A result for `url` with nothing detected and no probe run
+    pub(crate) fn empty(url: Url) -> Self {
+        Self {
+            final_url: url.clone(),
+            url,
+            wordpress_detected: false,
+            wordpress_version: None,
+            wordpress_version_estimate: None,
+            wordpress_latest: None,
+            theme: None,
+            plugins: Vec::new(),
+            page_builder: None,
+            debug_log_exposed: None,
+            exposed_backups: Vec::new(),
+            woocommerce: None,
+            cdn_host: None,
+            cdn: None,
+            content_dir: None,
+            is_multisite: None,
+            locale: None,
+            login_accessible: None,
+            login_relocated: None,
+            login_url: None,
+            xmlrpc_url: None,
+            wp_cron_exposed: None,
+            file_editor_blocked: None,
+            rest_api_status: None,
+            rest_namespaces: Vec::new(),
+            rest_route_namespaces: Vec::new(),
+            php_version: None,
+            server: None,
+            maintenance_mode: None,
+            partial: false,
+            truncated: false,
+            failed_probes: Vec::new(),
+            api_rate_limited: 0,
+            evidence: Vec::new(),
+            detection_methods: BTreeSet::new(),
+        }
+    }
+
+    /// Check if any detection method found something
+    fn detected_anything(&self) -> bool {
+        self.wordpress_detected || self.theme.is_some() || !self.plugins.is_empty()
+    }
+
+    /// How far to trust the result, from how many independent methods fired
+    ///
+    /// Counts the [`DetectionMethod`]s in
+    /// [`detection_methods`](Self::detection_methods). Three or more is
+    /// [`Confidence::High`], two [`Confidence::Medium`], and one or none
+    /// [`Confidence::Low`].
+    pub fn confidence(&self) -> Confidence {
+        match self.detection_methods.len() {
+            0 | 1 => Confidence::Low,
+            2 => Confidence::Medium,
+            _ => Confidence::High,
+        }
+    }
+}
+
+/// An independent way WordPress was detected (see [`ScanResult::confidence`])
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DetectionMethod {
+    /// Core version from the generator tag, feed, readme, or core assets
+    Version,
+    /// WordPress namespaces in the REST API root
+    RestApi,
+    /// A bare generator tag, `wp_head()` links, cookies, or response headers
+    Markers,
+    /// `/wp-includes/` and `/wp-content/` paths, the theme, and plugins.
+    /// They all come from the same page markup, so they count once.
+    AssetPaths,
+}
+
+/// Confidence in a scan result (see [`ScanResult::confidence`])
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Confidence {
+    /// One weak signal or none
+    #[default]
+    Low,
+    /// Two independent signals agree
+    Medium,
+    /// Three or more independent signals agree
+    High,
+}
+
+impl std::fmt::Display for Confidence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Low => write!(f, "Low"),
+            Self::Medium => write!(f, "Medium"),
+            Self::High => write!(f, "High"),
+        }
+    }
+}
+
+/// Raw WordPress signals from [`Scanner::probe`]
+///
+/// Each flag is checked independently, so several may fire at once.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProbeResult {
+    /// Homepage has a `WordPress` generator meta tag
+    pub generator_present: bool,
+    /// `/wp-json/` answers with a WordPress REST API index
+    pub rest_api: bool,
+    /// Homepage sets a WordPress cookie
+    pub cookies: bool,
+    /// Homepage headers carry a WordPress fingerprint (`Link`, `X-Pingback`, `X-Powered-By`)
+    pub headers: bool,
+    /// Homepage loads assets from `/wp-includes/`
+    pub wp_includes: bool,
+    /// Homepage links to the RSD, Windows Live Writer, or pingback endpoints
+    pub pingback: bool,
+}
+
+impl ProbeResult {
+    /// Whether any signal fired
+    pub fn is_wordpress(&self) -> bool {
+        self.generator_present
+            || self.rest_api
+            || self.cookies
+            || self.headers
+            || self.wp_includes
+            || self.pingback
+    }
+}
+
+/// Availability of the WordPress REST API (`/wp-json/`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestApiStatus {
+    /// REST API responds publicly
+    Open,
+    /// REST API exists but requires authentication (401/403)
+    Restricted,
+    /// REST API not found (404 or a non-JSON page)
+    Absent,
+}
+
+/// WooCommerce store information
+#[derive(Debug, Clone)]
+pub struct WooCommerceInfo {
+    /// Whether the public Store API (`/wc/store/v1/products`) is reachable
+    pub store_api_public: bool,
 }
 
 /// Theme information
@@ -62,6 +567,21 @@ pub struct ThemeInfo {
     pub version: Option<String>,
     /// Latest version from WordPress.org
     pub latest_version: Option<String>,
+    /// `Version:` from the theme's `style.css` when it disagrees with `version`
+    pub disputed_version: Option<String>,
+    /// Date of the last WordPress.org release (`YYYY-MM-DD`)
+    pub last_updated: Option<String>,
+    /// Newest WordPress version the theme is tested with, per WordPress.org
+    pub tested_up_to: Option<String>,
+}
+
+/// Page builder information
+#[derive(Debug, Clone)]
+pub struct PageBuilderInfo {
+    /// Builder name (e.g., `Elementor`)
+    pub name: String,
+    /// Version if the builder's generator tag carries one
+    pub version: Option<String>,
 }
 
 /// Plugin information
@@ -73,18 +593,34 @@ pub struct PluginInfo {
     pub version: Option<String>,
     /// Latest version from WordPress.org
     pub latest_version: Option<String>,
+    /// Active installs reported by WordPress.org (None for premium/unlisted plugins)
+    pub active_installs: Option<u64>,
+    /// Whether the plugin is in the WordPress.org directory (None if the
+    /// lookup failed or was skipped); unlisted plugins are premium or custom
+    pub listed: Option<bool>,
+    /// Date of the last WordPress.org release (`YYYY-MM-DD`)
+    pub last_updated: Option<String>,
+    /// Newest WordPress version the plugin is tested with, per WordPress.org
+    pub tested_up_to: Option<String>,
 }
 
 /// WordPress.org plugin API response
 #[derive(Debug, Deserialize)]
 struct PluginApiResponse {
     version: Option<String>,
+    active_installs: Option<u64>,
+    /// e.g. `2024-01-15 3:45pm GMT`
+    last_updated: Option<String>,
+    tested: Option<String>,
 }
 
 /// WordPress.org theme API response
 #[derive(Debug, Deserialize)]
 struct ThemeApiResponse {
     version: Option<String>,
+    /// e.g. `2024-01-15`
+    last_updated: Option<String>,
+    tested: Option<String>,
 }
 
 /// WordPress version check API response
@@ -107,20 +643,231 @@ struct WpJsonResponse {
     url: Option<String>,
     /// Available namespaces (e.g., ["wp/v2", "oembed/1.0"])
     namespaces: Option<Vec<String>>,
+    /// Registered routes, keyed by path pattern (contents unused)
+    routes: Option<HashMap<String, serde::de::IgnoredAny>>,
 }
 
-/// WordPress scanner
+/// A fetched page along with response metadata needed for detection
 #[derive(Debug)]
+struct FetchedPage {
+    /// Decoded body
+    html: String,
+    /// URL the page was served from after redirects
+    final_url: Url,
+    /// Response headers
+    headers: HeaderMap,
+}
+
+/// Progress event emitted during a scan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanProgress {
+    /// Fetching the homepage
+    FetchingHomepage,
+    /// Detecting the WordPress version
+    DetectingVersion,
+    /// Detecting the theme
+    DetectingTheme,
+    /// Detecting plugins from the page
+    DetectingPlugins,
+    /// Looking up a specific plugin
+    CheckingPlugin(String),
+    /// Checking for exposed files
+    CheckingExposures,
+    /// Scan finished
+    Done,
+}
+
+/// Callback receiving scan progress events
+#[derive(Clone)]
+struct ProgressCallback(Arc<dyn Fn(ScanProgress) + Send + Sync>);
+
+impl std::fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ProgressCallback")
+    }
+}
+
+/// Paths probed for the REST API and WordPress version
+///
+/// Relative paths resolve against the install directory; a leading `/`
+/// resolves against the host root instead. The defaults match a stock
+/// install, and changing them affects detection accuracy: a wrong path
+/// just means that source finds nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectionPaths {
+    /// REST API root (default: `wp-json/`)
+    pub rest_api: String,
+    /// RSS feed carrying the generator tag (default: `feed/`)
+    pub feed: String,
+    /// Readme carrying the version line (default: `readme.html`)
+    pub readme: String,
+}
+
+impl Default for DetectionPaths {
+    fn default() -> Self {
+        Self {
+            rest_api: WP_JSON_PATH.to_string(),
+            feed: WP_FEED_PATH.to_string(),
+            readme: WP_README_PATH.to_string(),
+        }
+    }
+}
+
+/// HTTP clients for target-site and WordPress.org API requests
+///
+/// Kept separate so target-specific settings (source address, redirect
+/// policy) never apply to API lookups.
+#[derive(Debug, Clone)]
+struct HttpClients {
+    target: Client,
+    api: Client,
+}
+
+/// Hostname lookup used by [`GuardedResolver`]
+type LookupFn = Arc<dyn Fn(&str) -> std::io::Result<Vec<IpAddr>> + Send + Sync>;
+
+/// DNS resolver that rejects internal addresses when a connection is made
+///
+/// The build-time host check resolves the name once, so a DNS-rebinding
+/// server could answer with a public IP then and a private one when the
+/// request is sent. Checking every resolution the client actually connects
+/// to closes that gap, including for redirects.
+#[derive(Clone)]
+struct GuardedResolver {
+    allowed_hosts: Vec<String>,
+    lookup: LookupFn,
+}
+
+impl GuardedResolver {
+    /// Resolver backed by the system resolver
+    fn system(allowed_hosts: Vec<String>) -> Self {
+        Self {
+            allowed_hosts,
+            lookup: Arc::new(|host| {
+                (host, 0)
+                    .to_socket_addrs()
+                    .map(|addrs| addrs.map(|addr| addr.ip()).collect())
+            }),
+        }
+    }
+}
+
+impl Resolve for GuardedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let resolver = self.clone();
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let lookup = resolver.lookup.clone();
+            let ips = tokio::task::spawn_blocking(move || lookup(&host)).await??;
+
+            if !Scanner::is_allowed_host(name.as_str(), &resolver.allowed_hosts)
+                && let Some(ip) = ips.iter().find(|ip| Scanner::is_internal_ip(**ip))
+            {
+                return Err(format!(
+                    "{} resolved to internal/private IP address: {}",
+                    name.as_str(),
+                    ip
+                )
+                .into());
+            }
+
+            let addrs: Addrs = Box::new(ips.into_iter().map(|ip| SocketAddr::new(ip, 0)));
+            Ok(addrs)
+        })
+    }
+}
+
+/// Outcome of a WordPress.org API lookup
+#[derive(Debug, Clone)]
+enum ApiLookup<T> {
+    /// The API answered with the requested details
+    Found(T),
+    /// The API answered 404 or with an error object: the slug isn't listed
+    NotListed,
+    /// The request failed, the response was unreadable, or lookups are off
+    Unavailable,
+}
+
+/// A WordPress.org API lookup result and when it was fetched
+#[derive(Debug, Clone)]
+struct CachedLookup {
+    value: ApiLookup<serde_json::Value>,
+    fetched_at: Instant,
+}
+
+/// Cache of WordPress.org API lookups, keyed by request URL
+type ApiCache = Arc<Mutex<HashMap<String, CachedLookup>>>;
+
+/// Failures and evidence recorded while a single scan runs
+#[derive(Debug, Default)]
+struct ScanRecords {
+    failed_probes: Vec<String>,
+    evidence: Vec<Evidence>,
+    api_rate_limited: usize,
+}
+
+/// Per-scan records shared by the scanner's detection methods
+type SharedRecords = Arc<Mutex<ScanRecords>>;
+
+/// WordPress scanner
+#[derive(Debug, Clone)]
 pub struct Scanner {
     client: Client,
+    api_client: Client,
+    api_cache: ApiCache,
+    cache_ttl: Option<Duration>,
+    api_base: String,
     base_url: Url,
+    records: SharedRecords,
+    requests: Arc<AtomicUsize>,
+    max_requests: Option<usize>,
+    max_retries: u32,
+    plugin_wordlist: Vec<String>,
+    backup_files: Vec<String>,
+    detection_paths: DetectionPaths,
+    skip_plugins: HashSet<String>,
+    concurrency: usize,
+    max_body_bytes: usize,
+    strict_body_size: bool,
+    offline: bool,
+    version_only: bool,
+    file_edit_probe: bool,
+    total_timeout: Option<Duration>,
+    on_progress: Option<ProgressCallback>,
 }
 
 /// Builder for configuring a Scanner with options
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ScannerBuilder {
     url: String,
+    client: Option<Client>,
+    api_base: String,
+    cache_ttl: Option<Duration>,
     allow_private: bool,
+    allowed_hosts: Vec<String>,
+    local_address: Option<IpAddr>,
+    proxy: Option<String>,
+    root_certificates: Vec<String>,
+    accept_invalid_certs: bool,
+    accept_language: Option<String>,
+    http1_only: bool,
+    rebinding_protection: bool,
+    max_redirects: usize,
+    timeout: Option<Duration>,
+    plugin_wordlist: Vec<String>,
+    backup_files: Vec<String>,
+    detection_paths: DetectionPaths,
+    skip_plugins: Vec<String>,
+    concurrency: usize,
+    max_body_bytes: usize,
+    strict_body_size: bool,
+    offline: bool,
+    version_only: bool,
+    file_edit_probe: bool,
+    total_timeout: Option<Duration>,
+    max_requests: Option<usize>,
+    max_retries: u32,
+    on_progress: Option<ProgressCallback>,
 }
 
 impl ScannerBuilder {
@@ -128,7 +875,34 @@ impl ScannerBuilder {
     pub fn new(url: &str) -> Self {
         Self {
             url: url.to_string(),
+            client: None,
+            api_base: WP_API_BASE.to_string(),
+            cache_ttl: None,
             allow_private: false,
+            allowed_hosts: Vec::new(),
+            local_address: None,
+            proxy: None,
+            root_certificates: Vec::new(),
+            accept_invalid_certs: false,
+            accept_language: None,
+            http1_only: false,
+            rebinding_protection: true,
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+            timeout: None,
+            plugin_wordlist: Vec::new(),
+            backup_files: DEFAULT_BACKUP_FILES.iter().map(|f| f.to_string()).collect(),
+            detection_paths: DetectionPaths::default(),
+            skip_plugins: Vec::new(),
+            concurrency: DEFAULT_CONCURRENCY,
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            strict_body_size: false,
+            offline: false,
+            version_only: false,
+            file_edit_probe: false,
+            total_timeout: None,
+            max_requests: None,
+            max_retries: DEFAULT_MAX_RETRIES,
+            on_progress: None,
         }
     }
 
@@ -141,9 +915,351 @@ impl ScannerBuilder {
         self
     }
 
+    /// Allow scanning one specific private/internal host (e.g., `10.0.0.5`)
+    ///
+    /// The host must match exactly (hostname or literal IP). SSRF protection
+    /// stays in place for every other host. Can be called multiple times.
+    pub fn allow_host(mut self, host: &str) -> Self {
+        self.allowed_hosts.push(host.to_string());
+        self
+    }
+
+    /// Re-check resolved addresses on every connection (default: on)
+    ///
+    /// Guards against DNS rebinding, where a host resolves to a public IP when
+    /// the scanner is built and to an internal one when requests are sent.
+    /// Only applies when private addresses are not allowed and no proxy is
+    /// set (the proxy resolves target hosts itself). This check also covers
+    /// host names reached through redirects, which are otherwise only
+    /// checked when they are IP literals or `localhost`.
+    pub fn rebinding_protection(mut self, enabled: bool) -> Self {
+        self.rebinding_protection = enabled;
+        self
+    }
+
+    /// Bind requests to a specific local source address
+    ///
+    /// Useful on multi-homed hosts that must scan from a particular interface.
+    /// This only affects requests to the target site; WordPress.org API
+    /// lookups use the default route.
+    pub fn local_address(mut self, addr: IpAddr) -> Self {
+        self.local_address = Some(addr);
+        self
+    }
+
+    /// Route target-site requests through a proxy (e.g., `socks5h://127.0.0.1:9050`)
+    ///
+    /// Accepts `http`, `https`, `socks5`, and `socks5h` proxy URLs. With a
+    /// `socks5h` proxy, `.onion` hosts skip the DNS-based internal-address
+    /// check, since they only resolve inside the proxy. Scanning a `.onion`
+    /// host through any other proxy, or none, fails to build: the name would
+    /// be resolved (and leaked) locally. Clearnet hosts keep the usual SSRF
+    /// protection.
+    /// WordPress.org API lookups don't use the proxy.
+    pub fn proxy(mut self, url: &str) -> Self {
+        self.proxy = Some(url.to_string());
+        self
+    }
+
+    /// Trust an additional root certificate (a PEM string, or a PEM/DER file path)
+    ///
+    /// For TLS-intercepting corporate proxies that present their own CA. The
+    /// certificate is added to the system trust store, not substituted for it,
+    /// and applies to both target-site and WordPress.org requests. Load and
+    /// parse failures surface as [`Error::HttpClient`] when the scanner is
+    /// built. Can be called multiple times.
+    pub fn add_root_certificate(mut self, path_or_pem: &str) -> Self {
+        self.root_certificates.push(path_or_pem.to_string());
+        self
+    }
+
+    /// Accept invalid TLS certificates from the target site (default: off)
+    ///
+    /// Self-signed, expired, and wrong-host certificates are accepted, which
+    /// also means a man in the middle goes unnoticed. Meant for staging and
+    /// local audits; prefer [`add_root_certificate`](Self::add_root_certificate)
+    /// when the issuing CA is known. WordPress.org API lookups stay strict.
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.accept_invalid_certs = accept;
+        self
+    }
+
+    /// Send an `Accept-Language` header to the target site (default: none)
+    ///
+    /// Multilingual sites may serve different markup per language, and some
+    /// content is gated by it. WordPress.org API lookups never carry the
+    /// header. Conflicts with [`with_client`](Self::with_client).
+    pub fn accept_language(mut self, language: &str) -> Self {
+        self.accept_language = Some(language.to_string());
+        self
+    }
+
+    /// Talk HTTP/1.1 only to the target site (default: off, negotiate HTTP/2)
+    ///
+    /// A workaround for servers and WAFs that misbehave over HTTP/2, where a
+    /// site works in a browser but not in the scanner. HTTP/1.1 gives up
+    /// multiplexing, so scans with many probes run a little slower.
+    /// WordPress.org API lookups keep negotiating. Conflicts with
+    /// [`with_client`](Self::with_client).
+    pub fn http1_only(mut self, http1_only: bool) -> Self {
+        self.http1_only = http1_only;
+        self
+    }
+
+    /// Whether `.onion` hosts are resolved by a configured `socks5h` proxy
+    fn onion_via_proxy(&self) -> bool {
+        self.client.is_none()
+            && self
+                .proxy
+                .as_deref()
+                .is_some_and(|proxy| proxy.to_ascii_lowercase().starts_with("socks5h://"))
+    }
+
+    /// Maximum number of redirects to follow (default: 10)
+    ///
+    /// Unless private addresses are allowed, redirects into private/internal
+    /// IP space are rejected regardless of this limit.
+    pub fn max_redirects(mut self, max: usize) -> Self {
+        self.max_redirects = max;
+        self
+    }
+
+    /// Timeout of each request, to the target and WordPress.org (default: 30s)
+    ///
+    /// A zero timeout is rejected by [`build`](Self::build). See
+    /// [`total_timeout`](Self::total_timeout) to bound the whole scan.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Use an existing `reqwest::Client` instead of building one
+    ///
+    /// The client is used for both target-site and WordPress.org requests.
+    /// URL validation (scheme and SSRF checks) still applies, but the
+    /// redirect-time private IP check does not. Options that configure the
+    /// internal client ([`local_address`](Self::local_address),
+    /// [`proxy`](Self::proxy), [`add_root_certificate`](Self::add_root_certificate),
+    /// [`danger_accept_invalid_certs`](Self::danger_accept_invalid_certs),
+    /// [`accept_language`](Self::accept_language),
+    /// [`http1_only`](Self::http1_only), [`max_redirects`](Self::max_redirects),
+    /// [`timeout`](Self::timeout))
+    /// conflict with it; configure timeouts, user agent, proxies and redirect
+    /// policy on the client itself.
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Use a different WordPress.org API base URL (default: `https://api.wordpress.org`)
+    ///
+    /// Useful for internal mirrors or test fixture servers. The URL is
+    /// validated when the scanner is built.
+    pub fn api_base(mut self, url: &str) -> Self {
+        self.api_base = url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Re-fetch cached WordPress.org lookups older than `ttl` (default: never)
+    ///
+    /// Lookups are cached per URL and shared by every scan built from the same
+    /// configuration ([`Scanner::scan_many`], [`Scanner::scan_stream`], clones
+    /// of a scanner). Long-running services should set a TTL so new releases
+    /// are picked up.
+    pub fn cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Probe for plugins by slug, in addition to HTML detection
+    ///
+    /// Issues a HEAD request to `/wp-content/plugins/{slug}/` for each candidate
+    /// not already found in the page. 200 and 403 count as present, anything
+    /// else as absent. Slugs with characters outside `[a-zA-Z0-9_-]` are ignored.
+    pub fn plugin_wordlist(mut self, slugs: Vec<String>) -> Self {
+        self.plugin_wordlist = slugs;
+        self
+    }
+
+    /// Backup file names to probe in the install directory
+    ///
+    /// Replaces the default list (`wp-config.php.bak`, `.wp-config.php.swp`,
+    /// `database.sql`, `backup.zip`); pass an empty list to skip the probe.
+    /// Each name costs one HEAD request, so keep the list short.
+    pub fn backup_files(mut self, names: Vec<String>) -> Self {
+        self.backup_files = names;
+        self
+    }
+
+    /// Override the REST API, feed, and readme paths used for detection
+    ///
+    /// For sites that relocate these (custom feed URL, renamed readme) or
+    /// mock servers with nonstandard routes (default: [`DetectionPaths::default`]).
+    pub fn detection_paths(mut self, paths: DetectionPaths) -> Self {
+        self.detection_paths = paths;
+        self
+    }
+
+    /// Ignore additional plugin slugs during detection
+    ///
+    /// Extends the built-in skip list (`index`, `cache`), which stays in
+    /// effect. Useful for path segments that look like plugins but aren't,
+    /// such as CDN or cache directories. Can be called multiple times.
+    pub fn skip_plugins(mut self, slugs: Vec<String>) -> Self {
+        self.skip_plugins.extend(slugs);
+        self
+    }
+
+    /// Maximum number of concurrent requests when probing paths (default: 8)
+    pub fn concurrency(mut self, limit: usize) -> Self {
+        self.concurrency = limit.max(1);
+        self
+    }
+
+    /// Maximum number of bytes read from a page body (default: 5 MiB)
+    ///
+    /// Longer bodies are truncated, or rejected with [`Error::BodyTooLarge`]
+    /// when [`strict_body_size`](Self::strict_body_size) is enabled.
+    pub fn max_body_bytes(mut self, max: usize) -> Self {
+        self.max_body_bytes = max;
+        self
+    }
+
+    /// Fail with [`Error::BodyTooLarge`] instead of truncating oversized bodies
+    pub fn strict_body_size(mut self, strict: bool) -> Self {
+        self.strict_body_size = strict;
+        self
+    }
+
+    /// Skip all WordPress.org API lookups (detection only)
+    ///
+    /// No requests are sent to the API, so latest versions stay `None` and
+    /// components are reported as Ok or Unknown, never Outdated.
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Detect only the WordPress core version (default: off)
+    ///
+    /// A fast mode for bulk core-version surveys: the homepage, the core
+    /// version chain, and the latest-version lookup run, while theme, plugin,
+    /// and exposure probes are skipped. The result has no theme or plugins and
+    /// leaves every other check as `None`.
+    pub fn version_only(mut self, version_only: bool) -> Self {
+        self.version_only = version_only;
+        self
+    }
+
+    /// Probe `wp-admin/theme-editor.php` for blocked file editing (default: off)
+    ///
+    /// `DISALLOW_FILE_EDIT` can't be read remotely, but a theme editor that
+    /// answers 403/404 instead of redirecting to the login page hints that
+    /// editing is locked down. This is a low-confidence signal, and some WAFs
+    /// flag unauthenticated admin-area requests, hence opt-in.
+    pub fn file_edit_probe(mut self, enabled: bool) -> Self {
+        self.file_edit_probe = enabled;
+        self
+    }
+
+    /// Hard limit on the duration of a whole scan (default: none)
+    ///
+    /// Each request already has its own timeout, but a site with many plugins
+    /// can still take minutes overall. When the limit is hit, the scan fails
+    /// with [`Error::ScanTimeout`] and results gathered so far are discarded.
+    pub fn total_timeout(mut self, timeout: Duration) -> Self {
+        self.total_timeout = Some(timeout);
+        self
+    }
+
+    /// Cap the number of HTTP requests a single scan sends (default: none)
+    ///
+    /// A guardrail for untrusted targets: a page referencing thousands of fake
+    /// plugin paths would otherwise trigger thousands of probes and
+    /// WordPress.org lookups. The homepage counts toward the budget. Once it is
+    /// spent, remaining requests are skipped and [`ScanResult::truncated`] is
+    /// set. Cached WordPress.org lookups are free. Each
+    /// [`Scanner::check_plugin`] and [`Scanner::check_theme`] call gets a
+    /// budget of its own as well.
+    pub fn max_requests(mut self, max: usize) -> Self {
+        self.max_requests = Some(max);
+        self
+    }
+
+    /// Retry target pages answered with 429 Too Many Requests (default: 2)
+    ///
+    /// A retry only happens when the response carries a `Retry-After` delay
+    /// in seconds of at most 30 seconds; otherwise the page fails right away
+    /// rather than hammering a rate-limited site. Waiting counts toward the
+    /// [total timeout](Self::total_timeout), and every retry toward the
+    /// [request budget](Self::max_requests). Pass 0 to never retry.
+    pub fn max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Register a callback that receives [`ScanProgress`] events during a scan
+    ///
+    /// Useful for spinners and progress bars. Nothing is emitted when no
+    /// callback is registered.
+    pub fn on_progress(mut self, callback: impl Fn(ScanProgress) + Send + Sync + 'static) -> Self {
+        self.on_progress = Some(ProgressCallback(Arc::new(callback)));
+        self
+    }
+
     /// Build the Scanner with the configured options
+    ///
+    /// Contradictory options fail with [`Error::ConflictingOptions`] rather
+    /// than one of them being silently ignored:
+    ///
+    /// - [`with_client`](Self::with_client) with any option that configures
+    ///   the internal client
+    /// - [`version_only`](Self::version_only) with a
+    ///   [`plugin_wordlist`](Self::plugin_wordlist) or
+    ///   [`file_edit_probe`](Self::file_edit_probe), which it skips
     pub fn build(self) -> Result<Scanner> {
-        Scanner::build_internal(&self.url, self.allow_private)
+        Scanner::build_internal(self)
+    }
+
+    /// Reject option combinations where one setting would be ignored
+    fn validate(&self) -> Result<()> {
+        let conflicts: [(bool, &str, &[_]); 2] = [
+            (
+                self.client.is_some(),
+                "with_client",
+                &[
+                    ("local_address", self.local_address.is_some()),
+                    ("proxy", self.proxy.is_some()),
+                    ("add_root_certificate", !self.root_certificates.is_empty()),
+                    ("danger_accept_invalid_certs", self.accept_invalid_certs),
+                    ("accept_language", self.accept_language.is_some()),
+                    ("http1_only", self.http1_only),
+                    ("max_redirects", self.max_redirects != DEFAULT_MAX_REDIRECTS),
+                    ("timeout", self.timeout.is_some()),
+                ],
+            ),
+            (
+                self.version_only,
+                "version_only",
+                &[
+                    ("plugin_wordlist", !self.plugin_wordlist.is_empty()),
+                    ("file_edit_probe", self.file_edit_probe),
+                ],
+            ),
+        ];
+
+        for (enabled, option, others) in conflicts {
+            if !enabled {
+                continue;
+            }
+            if let Some((other, _)) = others.iter().find(|(_, set)| *set) {
+                return Err(Error::ConflictingOptions(format!(
+                    "{} has no effect with {}",
+                    other, option
+                )));
+            }
+        }
+        Ok(())
     }
 }
 
@@ -153,7 +1269,7 @@ impl Scanner {
     /// Uses default settings with SSRF protection enabled.
     /// For more options, use [`Scanner::builder()`].
     pub fn new(url: &str) -> Result<Self> {
-        Self::build_internal(url, false)
+        Self::build_internal(ScannerBuilder::new(url))
     }
 
     /// Create a builder for configuring scanner options
@@ -172,50 +1288,303 @@ impl Scanner {
         ScannerBuilder::new(url)
     }
 
-    /// Internal builder function
-    fn build_internal(url: &str, allow_private: bool) -> Result<Self> {
-        // Auto-add https:// if no scheme provided
-        let url_with_scheme = if !url.contains("://") {
-            format!("https://{}", url)
-        } else {
-            url.to_string()
+    /// Scan multiple sites, sharing one HTTP client and API cache
+    ///
+    /// Each URL is scanned in turn with the options from `config`; the URL
+    /// passed to the builder itself is ignored. Results are returned in input
+    /// order, so one failing site does not abort the others.
+    pub async fn scan_many(
+        urls: &[String],
+        config: &ScannerBuilder,
+    ) -> Vec<(String, Result<ScanResult>)> {
+        let clients = match Self::build_clients(config) {
+            Ok(clients) => clients,
+            Err(e) => {
+                let message = e.to_string();
+                return urls
+                    .iter()
+                    .map(|url| (url.clone(), Err(Error::HttpClient(message.clone()))))
+                    .collect();
+            }
         };
+        let api_cache = ApiCache::default();
 
-        let base_url =
-            Url::parse(&url_with_scheme).map_err(|e| Error::InvalidUrl(e.to_string()))?;
-
-        // Validate URL scheme (SSRF protection)
-        if !ALLOWED_SCHEMES.contains(&base_url.scheme()) {
-            return Err(Error::InvalidUrl(format!(
-                "scheme '{}' not allowed (use http or https)",
-                base_url.scheme()
-            )));
+        let mut results = Vec::with_capacity(urls.len());
+        for url in urls {
+            let site_config = ScannerBuilder {
+                url: url.clone(),
+                ..config.clone()
+            };
+            let result = match Self::build_with(site_config, clients.clone(), api_cache.clone()) {
+                Ok(scanner) => scanner.scan().await,
+                Err(e) => Err(e),
+            };
+            results.push((url.clone(), result));
         }
+        results
+    }
 
-        // Validate host is not internal/private (SSRF protection)
-        if !allow_private {
-            Self::validate_host(&base_url)?;
+    /// Scan a stream of sites concurrently, yielding analyses as they complete
+    ///
+    /// Like [`Scanner::scan_many`], all scans share one HTTP client and API
+    /// cache. At most `concurrency` sites are scanned at once, and new URLs
+    /// are only pulled from `urls` as results are consumed, so a slow
+    /// consumer holds back further scans. Results arrive in completion order,
+    /// paired with the URL they belong to.
+    pub fn scan_stream(
+        urls: impl Stream<Item = String>,
+        config: &ScannerBuilder,
+        concurrency: usize,
+    ) -> impl Stream<Item = (String, Result<Analysis>)> {
+        let config = config.clone();
+        let clients = Self::build_clients(&config).map_err(|e| e.to_string());
+        let api_cache = ApiCache::default();
+
+        urls.map(move |url| {
+            let site_config = ScannerBuilder {
+                url: url.clone(),
+                ..config.clone()
+            };
+            let scanner = clients
+                .clone()
+                .map_err(Error::HttpClient)
+                .and_then(|clients| Self::build_with(site_config, clients, api_cache.clone()));
+            async move {
+                let result = match scanner {
+                    Ok(scanner) => scanner
+                        .scan()
+                        .await
+                        .map(|scan| Analyzer::new(scan).analyze()),
+                    Err(e) => Err(e),
+                };
+                (url, result)
+            }
+        })
+        .buffer_unordered(concurrency.max(1))
+    }
+
+    /// Internal builder function
+    fn build_internal(config: ScannerBuilder) -> Result<Self> {
+        let clients = Self::build_clients(&config)?;
+        Self::build_with(config, clients, ApiCache::default())
+    }
+
+    /// Build the HTTP clients for target-site and API requests
+    fn build_clients(config: &ScannerBuilder) -> Result<HttpClients> {
+        config.validate()?;
+
+        // A supplied client takes precedence over all client options
+        if let Some(client) = &config.client {
+            return Ok(HttpClients {
+                target: client.clone(),
+                api: client.clone(),
+            });
         }
 
-        let client = Client::builder()
+        let certificates = config
+            .root_certificates
+            .iter()
+            .map(|cert| Self::load_certificate(cert))
+            .collect::<Result<Vec<_>>>()?;
+
+        // reqwest would fail every request at once rather than never time out
+        let timeout = config.timeout.unwrap_or(DEFAULT_TIMEOUT);
+        if timeout.is_zero() {
+            return Err(Error::HttpClient("timeout must be non-zero".to_string()));
+        }
+
+        // Decode compressed bodies so detection never runs against raw gzip/brotli bytes
+        let mut target = Client::builder()
             .user_agent(USER_AGENT)
-            .timeout(Duration::from_secs(TIMEOUT_SECS))
-            .danger_accept_invalid_certs(false)
+            .timeout(timeout)
+            .gzip(true)
+            .brotli(true)
+            .deflate(true)
+            .redirect(Self::redirect_policy(config))
+            .local_address(config.local_address)
+            .danger_accept_invalid_certs(config.accept_invalid_certs);
+        if let Some(language) = &config.accept_language {
+            let value = HeaderValue::from_str(language).map_err(|e| {
+                Error::HttpClient(format!("invalid Accept-Language '{}': {}", language, e))
+            })?;
+            target = target.default_headers(HeaderMap::from_iter([(ACCEPT_LANGUAGE, value)]));
+        }
+        if config.http1_only {
+            target = target.http1_only();
+        }
+        let mut api = Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(timeout)
+            .gzip(true)
+            .brotli(true)
+            .deflate(true);
+        for certificate in certificates {
+            target = target.add_root_certificate(certificate.clone());
+            api = api.add_root_certificate(certificate);
+        }
+        if let Some(proxy) = &config.proxy {
+            let proxy = Proxy::all(proxy)
+                .map_err(|e| Error::HttpClient(format!("invalid proxy '{}': {}", proxy, e)))?;
+            target = target.proxy(proxy);
+        } else if config.rebinding_protection && !config.allow_private {
+            let resolver = GuardedResolver::system(config.allowed_hosts.clone());
+            target = target.dns_resolver(Arc::new(resolver));
+        }
+        let target = target
             .build()
             .map_err(|e| Error::HttpClient(e.to_string()))?;
 
-        Ok(Self { client, base_url })
+        let api = api.build().map_err(|e| Error::HttpClient(e.to_string()))?;
+
+        Ok(HttpClients { target, api })
+    }
+
+    /// Load a root certificate from inline PEM or a PEM/DER file
+    fn load_certificate(path_or_pem: &str) -> Result<Certificate> {
+        let (source, bytes) = if path_or_pem.trim_start().starts_with("-----BEGIN") {
+            ("inline PEM", path_or_pem.as_bytes().to_vec())
+        } else {
+            let bytes = std::fs::read(path_or_pem).map_err(|e| {
+                Error::HttpClient(format!("cannot read certificate '{}': {}", path_or_pem, e))
+            })?;
+            (path_or_pem, bytes)
+        };
+
+        let is_pem = bytes.windows(10).any(|window| window == b"-----BEGIN");
+        let certificate = if is_pem {
+            Certificate::from_pem(&bytes)
+        } else {
+            Certificate::from_der(&bytes)
+        };
+        certificate
+            .map_err(|e| Error::HttpClient(format!("invalid certificate '{}': {}", source, e)))
+    }
+
+    /// Redirect policy enforcing the redirect limit and SSRF protection
+    fn redirect_policy(config: &ScannerBuilder) -> Policy {
+        let max_redirects = config.max_redirects;
+        let allow_private = config.allow_private;
+        let allowed_hosts = config.allowed_hosts.clone();
+
+        // The policy runs on the async runtime, so no DNS lookups here: host
+        // names are checked by the guarded resolver when the connection opens
+        Policy::custom(move |attempt| {
+            if attempt.previous().len() > max_redirects {
+                return attempt.error(format!("too many redirects (max {})", max_redirects));
+            }
+            if !allow_private
+                && let Err(e) = Self::validate_host_literal(attempt.url(), &allowed_hosts)
+            {
+                return attempt.error(format!("redirect rejected: {}", e));
+            }
+            attempt.follow()
+        })
+    }
+
+    /// Validate the target URL and assemble a scanner around an existing client
+    fn build_with(
+        config: ScannerBuilder,
+        clients: HttpClients,
+        api_cache: ApiCache,
+    ) -> Result<Self> {
+        let api_base = Url::parse(&config.api_base)
+            .map_err(|e| Error::InvalidUrl(format!("API base '{}': {}", config.api_base, e)))?;
+        if !ALLOWED_SCHEMES.contains(&api_base.scheme()) {
+            return Err(Error::InvalidUrl(format!(
+                "API base scheme '{}' not allowed (use http or https)",
+                api_base.scheme()
+            )));
+        }
+
+        let url = config.url.as_str();
+
+        // Auto-add https:// if no scheme provided
+        let url_with_scheme = if !url.contains("://") {
+            format!("https://{}", url)
+        } else {
+            url.to_string()
+        };
+
+        let mut base_url =
+            Url::parse(&url_with_scheme).map_err(|e| Error::InvalidUrl(e.to_string()))?;
+
+        // Treat the path as the install directory so relative joins stay inside it
+        // (e.g., https://example.com/blog -> https://example.com/blog/feed/)
+        if !base_url.path().ends_with('/') {
+            let path = format!("{}/", base_url.path());
+            base_url.set_path(&path);
+        }
+
+        // Validate URL scheme (SSRF protection)
+        if !ALLOWED_SCHEMES.contains(&base_url.scheme()) {
+            return Err(Error::InvalidUrl(format!(
+                "scheme '{}' not allowed (use http or https)",
+                base_url.scheme()
+            )));
+        }
+
+        // Hidden services must never be looked up by the local resolver
+        let onion = base_url
+            .host_str()
+            .is_some_and(|host| host.to_ascii_lowercase().ends_with(".onion"));
+        if onion && config.client.is_none() && !config.onion_via_proxy() {
+            return Err(Error::HttpClient(
+                ".onion hosts need a socks5h:// proxy, which resolves them remotely".to_string(),
+            ));
+        }
+
+        // Validate host is not internal/private (SSRF protection)
+        if !config.allow_private {
+            Self::validate_host(&base_url, &config.allowed_hosts, config.onion_via_proxy())?;
+        }
+
+        Ok(Self {
+            client: clients.target,
+            api_client: clients.api,
+            api_cache,
+            cache_ttl: config.cache_ttl,
+            api_base: config.api_base,
+            base_url,
+            records: SharedRecords::default(),
+            requests: Arc::default(),
+            max_requests: config.max_requests,
+            max_retries: config.max_retries,
+            plugin_wordlist: config.plugin_wordlist,
+            backup_files: config.backup_files,
+            detection_paths: config.detection_paths,
+            skip_plugins: config.skip_plugins.into_iter().collect(),
+            concurrency: config.concurrency,
+            max_body_bytes: config.max_body_bytes,
+            strict_body_size: config.strict_body_size,
+            offline: config.offline,
+            version_only: config.version_only,
+            file_edit_probe: config.file_edit_probe,
+            total_timeout: config.total_timeout,
+            on_progress: config.on_progress,
+        })
     }
 
     /// Validate that the host is not an internal/private address (SSRF protection)
-    fn validate_host(url: &Url) -> Result<()> {
+    ///
+    /// With `onion_via_proxy`, `.onion` hosts are accepted without a DNS lookup:
+    /// they can't resolve publicly, and looking them up would leak the name to
+    /// the local resolver.
+    fn validate_host(url: &Url, allowed_hosts: &[String], onion_via_proxy: bool) -> Result<()> {
+        Self::validate_host_literal(url, allowed_hosts)?;
         let host = url
             .host_str()
             .ok_or_else(|| Error::InvalidUrl("missing host".to_string()))?;
 
-        // Block localhost variants
-        if host == "localhost" || host.ends_with(".localhost") {
-            return Err(Error::InvalidUrl("localhost not allowed".to_string()));
+        // Allowlisted hosts and IP literals need no lookup
+        if Self::is_allowed_host(host, allowed_hosts)
+            || !matches!(url.host(), Some(url::Host::Domain(_)))
+        {
+            return Ok(());
+        }
+
+        // Hidden services are resolved by the SOCKS proxy, never locally
+        if onion_via_proxy && host.to_ascii_lowercase().ends_with(".onion") {
+            return Ok(());
         }
 
         // Resolve hostname to IP and check if it's internal
@@ -238,6 +1607,51 @@ impl Scanner {
         Ok(())
     }
 
+    /// Reject `localhost` names and internal IP literals, without a DNS lookup
+    fn validate_host_literal(url: &Url, allowed_hosts: &[String]) -> Result<()> {
+        let host = url
+            .host_str()
+            .ok_or_else(|| Error::InvalidUrl("missing host".to_string()))?;
+
+        // Explicitly allowlisted hosts bypass the internal-address checks
+        if Self::is_allowed_host(host, allowed_hosts) {
+            return Ok(());
+        }
+
+        // Block localhost variants
+        if host == "localhost" || host.ends_with(".localhost") {
+            return Err(Error::InvalidUrl("localhost not allowed".to_string()));
+        }
+
+        let ip = match url.host() {
+            Some(url::Host::Ipv4(ip)) => IpAddr::V4(ip),
+            Some(url::Host::Ipv6(ip)) => IpAddr::V6(ip),
+            _ => return Ok(()),
+        };
+        if Self::is_internal_ip(ip) {
+            return Err(Error::InvalidUrl(format!(
+                "internal/private IP address not allowed: {}",
+                ip
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check if a host matches an entry in the allowlist (hostname or literal IP)
+    fn is_allowed_host(host: &str, allowed_hosts: &[String]) -> bool {
+        // IPv6 hosts are bracketed in URLs (e.g., [::1])
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        let host_ip = host.parse::<IpAddr>().ok();
+
+        allowed_hosts.iter().any(|allowed| {
+            let allowed = allowed.trim_start_matches('[').trim_end_matches(']');
+            match (host_ip, allowed.parse::<IpAddr>().ok()) {
+                (Some(host_ip), Some(allowed_ip)) => host_ip == allowed_ip,
+                _ => host.eq_ignore_ascii_case(allowed),
+            }
+        })
+    }
+
     /// Check if an IP address is internal/private (RFC 1918, link-local, loopback, etc.)
     fn is_internal_ip(ip: IpAddr) -> bool {
         match ip {
@@ -265,437 +1679,5991 @@ impl Scanner {
     }
 
     /// Scan the WordPress site
+    ///
+    /// Fails with [`Error::ScanTimeout`] if a
+    /// [total timeout](ScannerBuilder::total_timeout) is set and exceeded.
+    #[instrument(level = "info", skip_all, fields(url = %self.base_url))]
     pub async fn scan(&self) -> Result<ScanResult> {
-        // Fetch homepage
-        let homepage_html = self.fetch_page(&self.base_url).await?;
+        self.with_total_timeout(self.scan_site()).await
+    }
+
+    /// Scan the site using a homepage the caller already fetched
+    ///
+    /// Skips the homepage request and runs HTML and header detection on
+    /// `html` and `headers` as if they were served from the scanner's URL.
+    /// The other probes (feed, REST API, exposures) and WordPress.org lookups
+    /// still go over the network unless the scanner is
+    /// [offline](ScannerBuilder::offline). Honors the total timeout like
+    /// [`scan`](Self::scan).
+    #[instrument(level = "info", skip_all, fields(url = %self.base_url))]
+    pub async fn scan_from_html(&self, html: &str, headers: HeaderMap) -> Result<ScanResult> {
+        let homepage = FetchedPage {
+            html: html.to_string(),
+            final_url: self.base_url.clone(),
+            headers,
+        };
+        let scanner = Self {
+            records: SharedRecords::default(),
+            requests: Arc::default(),
+            ..self.clone()
+        };
+        self.with_total_timeout(scanner.scan_homepage(homepage, self.base_url.clone()))
+            .await
+    }
+
+    /// Check whether the site runs WordPress, without a full scan
+    ///
+    /// Fetches the homepage and the REST API root and reports each
+    /// WordPress signal separately. No version lookups, theme or plugin
+    /// detection, or WordPress.org requests are made. Meant as a cheap
+    /// filter before [`scan`](Self::scan), e.g. in a crawler. Fails if the
+    /// homepage can't be fetched, and honors the total timeout.
+    #[instrument(level = "info", skip_all, fields(url = %self.base_url))]
+    pub async fn probe(&self) -> Result<ProbeResult> {
+        self.with_total_timeout(self.probe_site()).await
+    }
+
+    /// Probe the site without the total timeout
+    async fn probe_site(&self) -> Result<ProbeResult> {
+        let counted = Self {
+            requests: Arc::default(),
+            records: SharedRecords::default(),
+            ..self.clone()
+        };
+        let homepage = counted.fetch_full_page(&self.base_url).await?;
+        let scanner = Self {
+            base_url: Self::install_dir(&homepage.final_url),
+            ..counted
+        };
+
+        let document = Html::parse_document(&homepage.html);
+        let (_, rest_root) = scanner.fetch_rest_root(&homepage.headers).await;
+        Ok(ProbeResult {
+            generator_present: scanner.detect_wp_from_meta(&document).is_some(),
+            rest_api: scanner
+                .detect_wp_from_rest_api(rest_root.as_ref())
+                .is_some(),
+            cookies: scanner
+                .detect_wp_from_set_cookie(&homepage.headers)
+                .is_some(),
+            headers: scanner
+                .detect_wp_from_header_map(&homepage.headers)
+                .is_some(),
+            wp_includes: scanner.detect_wp_from_includes(&homepage.html).is_some(),
+            pingback: scanner.detect_wp_from_head_links(&document).is_some(),
+        })
+    }
+
+    /// Run a scan, failing with [`Error::ScanTimeout`] once the total timeout passes
+    async fn with_total_timeout<T>(&self, scan: impl Future<Output = Result<T>>) -> Result<T> {
+        match self.total_timeout {
+            Some(limit) => tokio::time::timeout(limit, scan)
+                .await
+                .map_err(|_| Error::ScanTimeout(limit))?,
+            None => scan.await,
+        }
+    }
+
+    /// Scan the site without the total timeout
+    async fn scan_site(&self) -> Result<ScanResult> {
+        // Every scan gets its own request budget, starting with the homepage
+        let counted = Self {
+            requests: Arc::default(),
+            ..self.clone()
+        };
+
+        // Fetch homepage, following redirects
+        self.progress(|| ScanProgress::FetchingHomepage);
+
+        // An error status (e.g., a 403 WAF challenge) doesn't end the scan: the
+        // feed, readme, and REST API may still be reachable. Connection-level
+        // errors still abort, since every other probe would fail the same way.
+        let (homepage, homepage_error) = match counted.fetch_full_page(&self.base_url).await {
+            Ok(page) => (page, None),
+            Err(e @ Error::HttpStatus(_)) => {
+                debug!(error = %e, "homepage fetch failed, continuing with other probes");
+                let page = FetchedPage {
+                    html: String::new(),
+                    final_url: self.base_url.clone(),
+                    headers: HeaderMap::new(),
+                };
+                (page, Some(e))
+            }
+            Err(e) => return Err(e),
+        };
+
+        // Resolve all further paths against where the content actually lives
+        let scanner = Self {
+            base_url: Self::install_dir(&homepage.final_url),
+            records: SharedRecords::default(),
+            ..counted
+        };
+        if let Some(e) = &homepage_error {
+            scanner.record_failure(&self.base_url, e);
+            // Core and splash page plugins answer 503 while maintenance is on
+            if matches!(e, Error::HttpStatus(503)) {
+                scanner.record_evidence("maintenance mode", "homepage status", "503");
+            }
+        }
+
+        let mut result = scanner
+            .scan_homepage(homepage, self.base_url.clone())
+            .await?;
+        if let Some(e) = homepage_error {
+            // Nothing to report without the homepage and without any other signal
+            if !result.detected_anything() {
+                return Err(e);
+            }
+            result.partial = true;
+            if matches!(e, Error::HttpStatus(503)) {
+                result.maintenance_mode = Some(true);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Send a request, unless this scan's request budget is spent
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        if let Some(max) = self.max_requests
+            && self.requests.fetch_add(1, Ordering::Relaxed) >= max
+        {
+            debug!(max, "request budget exhausted, skipping request");
+            return Err(Error::RequestBudgetExceeded(max));
+        }
+        request
+            .send()
+            .await
+            .map_err(|e| Error::HttpRequest(e.to_string()))
+    }
+
+    /// Whether a request was skipped because the budget ran out
+    fn budget_exhausted(&self) -> bool {
+        self.max_requests
+            .is_some_and(|max| self.requests.load(Ordering::Relaxed) > max)
+    }
+
+    /// Failed probes, evidence, and rate-limited lookups recorded so far
+    fn collected_records(&self) -> (Vec<String>, Vec<Evidence>, usize) {
+        self.records
+            .lock()
+            .map(|records| {
+                (
+                    records.failed_probes.clone(),
+                    records.evidence.clone(),
+                    records.api_rate_limited,
+                )
+            })
+            .unwrap_or_default()
+    }
+
+    /// Record a failed page fetch for [`ScanResult::failed_probes`]
+    fn record_failure(&self, url: &Url, error: &Error) {
+        if let Ok(mut records) = self.records.lock() {
+            records
+                .failed_probes
+                .push(format!("{}: {}", url.path(), error));
+        }
+    }
+
+    /// Record how a detection was made for [`ScanResult::evidence`]
+    fn record_evidence(&self, finding: impl Into<String>, source: &str, detail: impl Into<String>) {
+        let evidence = Evidence {
+            finding: finding.into(),
+            source: source.to_string(),
+            detail: detail.into(),
+        };
+        trace!(%evidence, "evidence recorded");
+        if let Ok(mut records) = self.records.lock() {
+            records.evidence.push(evidence);
+        }
+    }
+
+    /// Run detection against an already fetched homepage
+    async fn scan_homepage(&self, homepage: FetchedPage, url: Url) -> Result<ScanResult> {
+        let FetchedPage {
+            html: homepage_html,
+            final_url,
+            headers,
+        } = homepage;
         let document = Html::parse_document(&homepage_html);
+        let preload_urls = Self::extract_link_header_urls(&headers);
+        let php_version = Self::detect_php_version(&headers);
+        if let Some(version) = &php_version {
+            self.record_evidence(
+                format!("PHP version {}", version),
+                "X-Powered-By header",
+                format!("PHP/{}", version),
+            );
+        }
+        let server = Self::detect_server(&headers);
+        if let Some(server) = &server {
+            self.record_evidence(format!("server {}", server), "Server header", server);
+        }
+        let cdn = Self::detect_cdn(&headers);
+        if let Some((header, name)) = cdn {
+            self.record_evidence(format!("CDN {}", name), &format!("{} header", header), name);
+        }
+        let cdn = cdn.map(|(_, name)| name.to_string());
+        let locale = self.detect_locale(&document, &headers);
+        let xmlrpc_url = self.detect_xmlrpc_url(&document, &headers);
 
         // Detect WordPress version
+        self.progress(|| ScanProgress::DetectingVersion);
         let wordpress_version = self.detect_wp_version(&document).await;
 
-        // If version not found, try alternative detection methods
-        let wordpress_detected = wordpress_version.is_some()
-            || self.detect_wp_from_rest_api().await.is_some()
-            || self.detect_wp_from_cookies().await.is_some();
+        // REST API root (used for detection and namespace inspection)
+        let (rest_api_status, rest_root) = self.fetch_rest_root(&headers).await;
+        let rest_namespaces = rest_root
+            .as_ref()
+            .and_then(|root| root.namespaces.clone())
+            .unwrap_or_default();
+        let rest_route_namespaces: Vec<String> = Self::route_namespaces(rest_root.as_ref())
+            .into_iter()
+            .filter(|namespace| !rest_namespaces.contains(namespace))
+            .collect();
+
+        // Without an exact version, bound it by the REST routes the site offers
+        let wordpress_version_estimate = if wordpress_version.is_none() {
+            self.estimate_wp_version(rest_root.as_ref())
+        } else {
+            None
+        };
+
+        // Run every detector rather than stopping at the first hit, so the
+        // evidence (and the confidence built from it) shows all that agree
+        let signals = [
+            (DetectionMethod::Version, wordpress_version.is_some()),
+            // A bare generator tag, when it didn't already give the version
+            (
+                DetectionMethod::Markers,
+                wordpress_version.is_none() && self.detect_wp_from_meta(&document).is_some(),
+            ),
+            (
+                DetectionMethod::AssetPaths,
+                self.detect_wp_from_includes(&homepage_html).is_some(),
+            ),
+            (
+                DetectionMethod::Markers,
+                self.detect_wp_from_head_links(&document).is_some(),
+            ),
+            (
+                DetectionMethod::RestApi,
+                self.detect_wp_from_rest_api(rest_root.as_ref()).is_some(),
+            ),
+            (
+                DetectionMethod::Markers,
+                self.detect_wp_from_set_cookie(&headers).is_some(),
+            ),
+            (
+                DetectionMethod::Markers,
+                self.detect_wp_from_header_map(&headers).is_some(),
+            ),
+            // A CDN can strip cookies and headers, so asset paths stand in
+            (
+                DetectionMethod::AssetPaths,
+                cdn.is_some() && self.detect_wp_from_content_paths(&homepage_html).is_some(),
+            ),
+        ];
+        let mut detection_methods: BTreeSet<DetectionMethod> = signals
+            .into_iter()
+            .filter_map(|(method, fired)| fired.then_some(method))
+            .collect();
+        // A locked-down REST API is only a weak signal; it needs the asset
+        // paths to agree before it counts
+        if rest_api_status == Some(RestApiStatus::Restricted)
+            && cdn.is_none()
+            && self.detect_wp_from_content_paths(&homepage_html).is_some()
+        {
+            detection_methods.insert(DetectionMethod::AssetPaths);
+        }
+        let wordpress_detected = !detection_methods.is_empty();
 
         // Fetch latest WordPress version
         let wordpress_latest = self.fetch_wp_latest_version().await;
 
+        if self.version_only {
+            let (failed_probes, evidence, api_rate_limited) = self.collected_records();
+            self.progress(|| ScanProgress::Done);
+            return Ok(ScanResult {
+                wordpress_detected,
+                wordpress_version,
+                wordpress_version_estimate,
+                wordpress_latest,
+                cdn,
+                locale,
+                xmlrpc_url,
+                rest_api_status,
+                rest_namespaces,
+                rest_route_namespaces,
+                php_version,
+                server,
+                truncated: self.budget_exhausted(),
+                failed_probes,
+                api_rate_limited,
+                evidence,
+                detection_methods,
+                final_url,
+                ..ScanResult::empty(url)
+            });
+        }
+
+        // Detect a CDN serving wp-content assets
+        let cdn_host = self.detect_cdn_host(&homepage_html);
+
+        // Look for a renamed content directory when nothing references wp-content
+        let content_dir = if wordpress_detected && !homepage_html.contains("wp-content") {
+            self.detect_content_dir(&homepage_html)
+        } else {
+            None
+        };
+
         // Detect theme and fetch latest version
-        let theme = self.detect_theme(&document).await;
+        self.progress(|| ScanProgress::DetectingTheme);
+        let theme = self.detect_theme(&document, &preload_urls).await;
 
         // Detect plugins and fetch latest versions
-        let plugins = self.detect_plugins(&document).await;
+        self.progress(|| ScanProgress::DetectingPlugins);
+        let mut plugins = self
+            .detect_plugins(
+                &document,
+                &preload_urls,
+                &[rest_namespaces.as_slice(), rest_route_namespaces.as_slice()].concat(),
+                theme.as_ref(),
+            )
+            .await;
+
+        // The theme and plugins come from the same markup as the other asset paths
+        if theme.is_some() || !plugins.is_empty() {
+            detection_methods.insert(DetectionMethod::AssetPaths);
+        }
+
+        let page_builder = self.detect_page_builder(&document, &homepage_html);
+        let maintenance_mode = self.detect_maintenance_mode(&homepage_html);
+
+        // Detect WooCommerce and make sure it gets a version lookup
+        let woocommerce = self.detect_woocommerce(&document, rest_root.as_ref()).await;
+        if woocommerce.is_some() && !plugins.iter().any(|p| p.slug == WC_PLUGIN_SLUG) {
+            plugins.push(self.lookup_plugin(WC_PLUGIN_SLUG.to_string(), None).await);
+        }
 
+        // Detect multisite network
+        let is_multisite = if wordpress_detected {
+            self.detect_multisite(rest_root.as_ref()).await
+        } else {
+            None
+        };
+
+        // Check for exposed files and login page
+        self.progress(|| ScanProgress::CheckingExposures);
+        let debug_log_exposed = self.detect_debug_log().await;
+        let exposed_backups = self.detect_exposed_backups().await;
+        let login_accessible = self.detect_login_page().await;
+        let (login_relocated, login_url) = self
+            .detect_login_relocation(&document, login_accessible)
+            .await;
+        let wp_cron_exposed = self.detect_wp_cron().await;
+        let file_editor_blocked = if self.file_edit_probe {
+            self.detect_file_editor_blocked().await
+        } else {
+            None
+        };
+
+        let (failed_probes, evidence, api_rate_limited) = self.collected_records();
+
+        self.progress(|| ScanProgress::Done);
         Ok(ScanResult {
-            url: self.base_url.clone(),
+            url,
+            final_url,
             wordpress_detected,
             wordpress_version,
+            wordpress_version_estimate,
             wordpress_latest,
             theme,
             plugins,
+            page_builder,
+            debug_log_exposed,
+            exposed_backups,
+            woocommerce,
+            cdn_host,
+            cdn,
+            content_dir,
+            is_multisite,
+            locale,
+            login_accessible,
+            login_relocated,
+            login_url,
+            xmlrpc_url,
+            wp_cron_exposed,
+            file_editor_blocked,
+            rest_api_status,
+            rest_namespaces,
+            rest_route_namespaces,
+            php_version,
+            server,
+            maintenance_mode,
+            partial: false,
+            truncated: self.budget_exhausted(),
+            failed_probes,
+            api_rate_limited,
+            evidence,
+            detection_methods,
         })
     }
 
+    /// Check a single plugin without scanning the homepage
+    ///
+    /// Probes `/wp-content/plugins/{slug}/`, reads the installed version from
+    /// the `Stable tag:` of its `readme.txt`, and looks up the latest release.
+    /// A plugin with neither a listable directory nor a readme version is
+    /// reported as not detected; a `403` directory alone doesn't count, since
+    /// WAFs deny every path. Fails on slugs outside `[a-zA-Z0-9_-]` and when the site
+    /// can't be reached.
+    pub async fn check_plugin(&self, slug: &str) -> Result<ComponentAnalysis> {
+        self.check_component(ComponentType::Plugin, slug).await
+    }
+
+    /// Check a single theme without scanning the homepage
+    ///
+    /// Like [`check_plugin`](Self::check_plugin), but probes
+    /// `/wp-content/themes/{slug}/` and reads the `Version:` header of its
+    /// `style.css`.
+    pub async fn check_theme(&self, slug: &str) -> Result<ComponentAnalysis> {
+        self.check_component(ComponentType::Theme, slug).await
+    }
+
+    /// Probe one plugin or theme directory and analyze what it reveals
+    async fn check_component(
+        &self,
+        component_type: ComponentType,
+        slug: &str,
+    ) -> Result<ComponentAnalysis> {
+        // Every lookup gets its own request budget, like a scan
+        let counted = Self {
+            requests: Arc::default(),
+            ..self.clone()
+        };
+        counted.probe_component(component_type, slug).await
+    }
+
+    /// Body of [`check_component`](Self::check_component)
+    async fn probe_component(
+        &self,
+        component_type: ComponentType,
+        slug: &str,
+    ) -> Result<ComponentAnalysis> {
+        if !Self::is_valid_slug(slug) {
+            return Err(Error::InvalidUrl(format!("invalid slug '{}'", slug)));
+        }
+        let (dir, version_file) = match component_type {
+            ComponentType::Theme => ("themes", "style.css"),
+            _ => ("plugins", "readme.txt"),
+        };
+        let dir_url = self
+            .base_url
+            .join(&format!("wp-content/{}/{}/", dir, slug))
+            .map_err(|e| Error::InvalidUrl(e.to_string()))?;
+
+        let response = self.send(self.client.head(dir_url.as_str())).await?;
+        // A 403 alone proves nothing: WAFs and denied listings answer it for
+        // any path, so it needs the version file to back it up
+        let dir_exists = response.status().as_u16() == 200;
+
+        let version = match dir_url.join(version_file) {
+            Ok(url) => self.fetch_leading_bytes(&url).await.and_then(|body| {
+                let text = String::from_utf8_lossy(&body);
+                match component_type {
+                    ComponentType::Theme => Self::extract_header_comment_version(&text),
+                    _ => Self::extract_stable_tag(&text),
+                }
+            }),
+            Err(_) => None,
+        };
+        if !dir_exists && version.is_none() {
+            return Ok(ComponentAnalysis::not_detected(component_type, slug));
+        }
+
+        let latest_version = match component_type {
+            ComponentType::Theme => self.fetch_theme_latest_version(slug).await,
+            _ => self.fetch_plugin_latest_version(slug).await,
+        };
+        Ok(ComponentAnalysis::new(
+            component_type,
+            slug,
+            version,
+            latest_version,
+        ))
+    }
+
+    /// Extract the `Stable tag:` version from a plugin `readme.txt` header
+    fn extract_stable_tag(text: &str) -> Option<String> {
+        let re = Regex::new(r"(?mi)^\s*Stable tag:\s*v?([0-9][0-9A-Za-z._-]*)").ok()?;
+        let version = re.captures(text)?.get(1)?.as_str();
+        Some(Self::normalize_version(version))
+    }
+
+    /// Emit a progress event if a callback is registered
+    fn progress(&self, event: impl FnOnce() -> ScanProgress) {
+        if let Some(ProgressCallback(callback)) = &self.on_progress {
+            callback(event());
+        }
+    }
+
     /// Fetch latest WordPress version from API
+    #[instrument(level = "debug", skip_all, ret)]
     async fn fetch_wp_latest_version(&self) -> Option<String> {
-        let url = format!("{}/core/version-check/1.7/", WP_API_BASE);
-        let response: WpVersionResponse =
-            self.client.get(&url).send().await.ok()?.json().await.ok()?;
-        response.offers.first().map(|o| o.version.clone())
+        let url = format!("{}/core/version-check/1.7/", self.api_base);
+        self.cached_api_lookup(&url, |response: WpVersionResponse| {
+            response.offers.first().map(|o| o.version.clone())
+        })
+        .await
     }
 
     /// Fetch latest plugin version from WordPress.org API
+    #[instrument(level = "debug", skip(self), ret)]
     async fn fetch_plugin_latest_version(&self, slug: &str) -> Option<String> {
+        match self.fetch_plugin_info(slug).await {
+            ApiLookup::Found(info) => info.version,
+            ApiLookup::NotListed | ApiLookup::Unavailable => None,
+        }
+    }
+
+    /// Fetch plugin details (latest version, active installs) from WordPress.org API
+    async fn fetch_plugin_info(&self, slug: &str) -> ApiLookup<PluginApiResponse> {
         let url = format!(
             "{}/plugins/info/1.2/?action=plugin_information&slug={}",
-            WP_API_BASE, slug
+            self.api_base, slug
         );
-        let response: PluginApiResponse =
-            self.client.get(&url).send().await.ok()?.json().await.ok()?;
-        response.version
+        match self.cached_api_value(&url).await {
+            ApiLookup::Found(value) => {
+                serde_json::from_value(value).map_or(ApiLookup::Unavailable, ApiLookup::Found)
+            }
+            ApiLookup::NotListed => ApiLookup::NotListed,
+            ApiLookup::Unavailable => ApiLookup::Unavailable,
+        }
+    }
+
+    /// Build plugin info for a detected slug, with its WordPress.org details
+    async fn lookup_plugin(&self, slug: String, version: Option<String>) -> PluginInfo {
+        let mut plugin = PluginInfo {
+            slug,
+            version,
+            latest_version: None,
+            active_installs: None,
+            listed: None,
+            last_updated: None,
+            tested_up_to: None,
+        };
+        match self.fetch_plugin_info(&plugin.slug).await {
+            ApiLookup::Found(info) => {
+                plugin.latest_version = info.version;
+                plugin.active_installs = info.active_installs;
+                plugin.listed = Some(true);
+                plugin.last_updated = Self::api_date(info.last_updated);
+                plugin.tested_up_to = info.tested;
+            }
+            ApiLookup::NotListed => plugin.listed = Some(false),
+            ApiLookup::Unavailable => {}
+        }
+        plugin
     }
 
     /// Fetch latest theme version from WordPress.org API
+    #[instrument(level = "debug", skip(self), ret)]
     async fn fetch_theme_latest_version(&self, slug: &str) -> Option<String> {
+        self.fetch_theme_info(slug).await?.version
+    }
+
+    /// Fetch theme details (latest version, release dates) from WordPress.org API
+    async fn fetch_theme_info(&self, slug: &str) -> Option<ThemeApiResponse> {
         let url = format!(
             "{}/themes/info/1.2/?action=theme_information&slug={}",
-            WP_API_BASE, slug
+            self.api_base, slug
         );
-        let response: ThemeApiResponse =
-            self.client.get(&url).send().await.ok()?.json().await.ok()?;
-        response.version
-    }
-
-    /// Fetch a page and return its HTML
-    async fn fetch_page(&self, url: &Url) -> Result<String> {
-        let response = self
-            .client
-            .get(url.as_str())
-            .send()
+        self.cached_api_lookup(&url, |response: ThemeApiResponse| Some(response))
             .await
-            .map_err(|e| Error::HttpRequest(e.to_string()))?;
+    }
 
-        if !response.status().is_success() {
-            return Err(Error::HttpStatus(response.status().as_u16()));
+    /// Fill in a theme's latest version and release details from WordPress.org
+    async fn lookup_theme(&self, theme: &mut ThemeInfo) {
+        if let Some(info) = self.fetch_theme_info(&theme.slug).await {
+            theme.latest_version = info.version;
+            theme.last_updated = Self::api_date(info.last_updated);
+            theme.tested_up_to = info.tested;
         }
+    }
 
-        response
-            .text()
-            .await
-            .map_err(|e| Error::HttpRequest(e.to_string()))
+    /// Normalize a WordPress.org release date to `YYYY-MM-DD`
+    fn api_date(raw: Option<String>) -> Option<String> {
+        parse_api_date(&raw?).map(|date| date.to_string())
     }
 
-    /// Detect WordPress version from various sources
-    async fn detect_wp_version(&self, document: &Html) -> Option<String> {
-        // Try meta generator tag first
-        if let Some(version) = self.detect_version_from_meta(document) {
-            return Some(version);
+    /// Fetch a WordPress.org API URL, reusing earlier results for the same URL
+    ///
+    /// Always `None` in [offline](ScannerBuilder::offline) mode.
+    async fn cached_api_lookup<T: DeserializeOwned, R>(
+        &self,
+        url: &str,
+        extract: impl FnOnce(T) -> Option<R>,
+    ) -> Option<R> {
+        match self.cached_api_value(url).await {
+            ApiLookup::Found(value) => serde_json::from_value(value).ok().and_then(extract),
+            ApiLookup::NotListed | ApiLookup::Unavailable => None,
         }
+    }
 
-        // Try RSS feed
-        if let Some(version) = self.detect_version_from_feed().await {
-            return Some(version);
+    /// Fetch the raw JSON of a WordPress.org API URL through the cache
+    ///
+    /// The whole response is cached, so lookups extracting different fields
+    /// from the same URL share one request. A 404 or an `error` object means
+    /// the slug isn't listed, which is kept apart from a failed request.
+    async fn cached_api_value(&self, url: &str) -> ApiLookup<serde_json::Value> {
+        if self.offline {
+            trace!(url, "offline, skipping API lookup");
+            return ApiLookup::Unavailable;
+        }
+        if let Ok(cache) = self.api_cache.lock()
+            && let Some(cached) = cache.get(url)
+        {
+            let fresh = self
+                .cache_ttl
+                .is_none_or(|ttl| cached.fetched_at.elapsed() < ttl);
+            if fresh {
+                trace!(url, "API cache hit");
+                return cached.value.clone();
+            }
+            trace!(url, "API cache entry expired");
         }
 
-        // Try readme.html
-        self.detect_version_from_readme().await
+        debug!(url, "querying WordPress.org API");
+        let value = match self.api_get(url).await {
+            Ok(response) => {
+                let status = response.status();
+                match response.json::<serde_json::Value>().await {
+                    Ok(value) if value.get("error").is_some() && !status.is_server_error() => {
+                        ApiLookup::NotListed
+                    }
+                    Ok(value) if status.is_success() => ApiLookup::Found(value),
+                    _ if status == reqwest::StatusCode::NOT_FOUND => ApiLookup::NotListed,
+                    // Server errors and unreadable bodies are transient: not cached
+                    _ => return ApiLookup::Unavailable,
+                }
+            }
+            // Not cached, so a later lookup of the same URL can still succeed
+            Err(Error::ApiRateLimited) => {
+                if let Ok(mut records) = self.records.lock() {
+                    records.api_rate_limited += 1;
+                }
+                return ApiLookup::Unavailable;
+            }
+            // Timeouts, connection errors, and a spent budget only affect this
+            // request; other scans share the cache
+            Err(_) => return ApiLookup::Unavailable,
+        };
+
+        if let Ok(mut cache) = self.api_cache.lock() {
+            let entry = CachedLookup {
+                value: value.clone(),
+                fetched_at: Instant::now(),
+            };
+            cache.insert(url.to_string(), entry);
+        }
+        value
+    }
+
+    /// Send a WordPress.org API request, backing off while it answers 429
+    ///
+    /// Waits for `Retry-After` (in seconds, up to [`API_RETRY_AFTER_MAX`]) or
+    /// an exponential backoff, and fails with [`Error::ApiRateLimited`] once
+    /// the retries are used up.
+    async fn api_get(&self, url: &str) -> Result<reqwest::Response> {
+        let mut backoff = API_RATE_LIMIT_BACKOFF;
+        for attempt in 0..=API_RATE_LIMIT_RETRIES {
+            let response = self.send(self.api_client.get(url)).await?;
+            if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+                return Ok(response);
+            }
+            if attempt == API_RATE_LIMIT_RETRIES {
+                break;
+            }
+
+            let delay = Self::retry_after(response.headers()).unwrap_or(backoff);
+            if delay > API_RETRY_AFTER_MAX {
+                break;
+            }
+            warn!(url, delay = ?delay, "WordPress.org API rate limited (429), backing off");
+            tokio::time::sleep(delay).await;
+            backoff *= 2;
+        }
+
+        warn!(
+            url,
+            "WordPress.org API still rate limited, latest version unavailable"
+        );
+        Err(Error::ApiRateLimited)
+    }
+
+    /// Delay requested by a `Retry-After` header in seconds (HTTP dates are ignored)
+    fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+        headers
+            .get("retry-after")?
+            .to_str()
+            .ok()?
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .map(Duration::from_secs)
+    }
+
+    /// Fetch a page and return its HTML
+    ///
+    /// Failures are recorded in [`ScanResult::failed_probes`].
+    async fn fetch_page(&self, url: &Url) -> Result<String> {
+        self.fetch_full_page(url)
+            .await
+            .map(|page| page.html)
+            .inspect_err(|e| self.record_failure(url, e))
+    }
+
+    /// Fetch a page and return its HTML, final URL after redirects, and headers
+    #[instrument(level = "debug", skip_all, fields(url = %url))]
+    async fn fetch_full_page(&self, url: &Url) -> Result<FetchedPage> {
+        let mut response = self.send(self.client.get(url.as_str())).await?;
+
+        // Wait out a WAF rate limit when the site says how long
+        for _ in 0..self.max_retries {
+            if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+                break;
+            }
+            let Some(delay) = Self::retry_after(response.headers())
+                .filter(|delay| *delay <= TARGET_RETRY_AFTER_MAX)
+            else {
+                break;
+            };
+            warn!(url = %url, delay = ?delay, "target rate limited (429), retrying");
+            tokio::time::sleep(delay).await;
+            response = self.send(self.client.get(url.as_str())).await?;
+        }
+
+        debug!(status = response.status().as_u16(), final_url = %response.url(), "page response");
+        if !response.status().is_success() {
+            return Err(Error::HttpStatus(response.status().as_u16()));
+        }
+
+        let final_url = response.url().clone();
+        let headers = response.headers().clone();
+
+        // Read the body in chunks so oversized responses can't exhaust memory
+        let mut body = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| Error::HttpRequest(e.to_string()))?
+        {
+            let remaining = self.max_body_bytes - body.len();
+            if chunk.len() > remaining {
+                if self.strict_body_size {
+                    return Err(Error::BodyTooLarge(self.max_body_bytes));
+                }
+                debug!(limit = self.max_body_bytes, "response body truncated");
+                body.extend_from_slice(&chunk[..remaining]);
+                break;
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        trace!(bytes = body.len(), "page body read");
+
+        // Invalid UTF-8 shouldn't abort detection; replace bad sequences instead
+        let html = String::from_utf8_lossy(&body).into_owned();
+        Ok(FetchedPage {
+            html,
+            final_url,
+            headers,
+        })
+    }
+
+    /// Directory of a page URL, used as the base for relative joins
+    ///
+    /// `https://example.com/blog/index.php?p=1` -> `https://example.com/blog/`
+    fn install_dir(url: &Url) -> Url {
+        let mut dir = url.clone();
+        dir.set_query(None);
+        dir.set_fragment(None);
+        if let Some(pos) = dir.path().rfind('/') {
+            let path = dir.path()[..=pos].to_string();
+            dir.set_path(&path);
+        }
+        dir
+    }
+
+    /// Detect WordPress version from various sources
+    #[instrument(level = "debug", skip_all, ret)]
+    async fn detect_wp_version(&self, document: &Html) -> Option<String> {
+        // Try meta generator tag first
+        if let Some(version) = self.detect_version_from_meta(document) {
+            return Some(version);
+        }
+
+        // Try RSS feed
+        if let Some(version) = self.detect_version_from_feed().await {
+            return Some(version);
+        }
+
+        // Try readme.html
+        if let Some(version) = self.detect_version_from_readme().await {
+            return Some(version);
+        }
+
+        // Try ?ver= on core assets referenced by the homepage
+        if let Some(version) = self.detect_version_from_core_assets(document) {
+            return Some(version);
+        }
+
+        // Try the login page, which keeps its generator and asset versions
+        // on sites that strip them from the front end
+        self.detect_version_from_login().await
     }
 
     /// Detect version from meta generator tag
     fn detect_version_from_meta(&self, document: &Html) -> Option<String> {
-        let selector = Selector::parse("meta[name='generator']").ok()?;
+        let (version, content) = Self::generator_version(document)?;
 
-        for element in document.select(&selector) {
-            if let Some(content) = element.value().attr("content")
-                && content.starts_with("WordPress")
-            {
+        // Caching or injection can leave stale tags behind; the highest version is used
+        let mut versions: Vec<String> = Vec::new();
+        for (found, _) in Self::generator_versions(document) {
+            if !versions.contains(&found) {
+                versions.push(found);
+            }
+        }
+        if versions.len() > 1 {
+            self.record_evidence(
+                format!("conflicting WordPress versions {}", versions.join(", ")),
+                "meta[name=generator]",
+                format!("using highest ({})", version),
+            );
+        }
+
+        self.record_evidence(
+            format!("WordPress version {}", version),
+            "meta[name=generator]",
+            content,
+        );
+        Some(version)
+    }
+
+    /// Extract the version and full content of a `WordPress X.Y.Z` generator tag
+    ///
+    /// When several tags disagree, the highest version wins, since a stale tag
+    /// left by a cache is older than the running install.
+    fn generator_version(document: &Html) -> Option<(String, String)> {
+        Self::generator_versions(document)
+            .into_iter()
+            .rev()
+            .max_by(|a, b| compare_versions(&a.0, &b.0))
+    }
+
+    /// Versions and contents of all `WordPress X.Y.Z` generator tags, in page order
+    fn generator_versions(document: &Html) -> Vec<(String, String)> {
+        let Ok(selector) = Selector::parse("meta[name='generator']") else {
+            return Vec::new();
+        };
+
+        document
+            .select(&selector)
+            .filter_map(|element| element.value().attr("content"))
+            .filter_map(|content| {
                 // Extract version from "WordPress X.Y.Z"
                 let version = content.strip_prefix("WordPress ")?.trim();
-                if !version.is_empty() {
-                    return Some(version.to_string());
-                }
-            }
+                (!version.is_empty()).then(|| (version.to_string(), content.to_string()))
+            })
+            .collect()
+    }
+
+    /// Detect WordPress from a generator tag, even without a version
+    ///
+    /// Hardened sites sometimes leave a bare `WordPress` generator behind.
+    fn detect_wp_from_meta(&self, document: &Html) -> Option<()> {
+        let selector = Selector::parse("meta[name='generator']").ok()?;
+
+        let content = document
+            .select(&selector)
+            .filter_map(|element| element.value().attr("content"))
+            .find(|content| *content == "WordPress" || content.starts_with("WordPress "))?;
+        self.record_evidence("WordPress", "meta[name=generator]", content);
+        Some(())
+    }
+
+    /// Detect WordPress from the RSD, Windows Live Writer, and pingback links
+    ///
+    /// `wp_head()` prints `<link rel="EditURI" href=".../xmlrpc.php?rsd">`,
+    /// `<link rel="wlwmanifest" href=".../wlwmanifest.xml">`, and themes add
+    /// `<link rel="pingback" href=".../xmlrpc.php">`. They often survive the
+    /// removal of the generator tag.
+    fn detect_wp_from_head_links(&self, document: &Html) -> Option<()> {
+        let selector = Selector::parse("link[rel][href]").ok()?;
+        let (rel, href) = document
+            .select(&selector)
+            .filter_map(|link| Some((link.value().attr("rel")?, link.value().attr("href")?)))
+            .find(|(rel, href)| match rel.to_ascii_lowercase().as_str() {
+                "edituri" => href.contains("?rsd"),
+                "wlwmanifest" => href.ends_with("wlwmanifest.xml"),
+                "pingback" => href.contains(WP_XMLRPC_PATH),
+                _ => false,
+            })?;
+        self.record_evidence("WordPress", &format!("link[rel={}]", rel), href);
+        Some(())
+    }
+
+    /// Locate the XML-RPC endpoint the site advertises
+    ///
+    /// Security plugins may move it away from `xmlrpc.php`, so the pingback
+    /// link wins, then the RSD link (minus its `?rsd` query), then the
+    /// `X-Pingback` header. Only same-host URLs are accepted.
+    fn detect_xmlrpc_url(&self, document: &Html, headers: &HeaderMap) -> Option<String> {
+        let link = |rel: &str| {
+            let selector = Selector::parse("link[rel][href]").ok()?;
+            document
+                .select(&selector)
+                .find(|link| {
+                    link.value()
+                        .attr("rel")
+                        .is_some_and(|value| value.eq_ignore_ascii_case(rel))
+                })?
+                .value()
+                .attr("href")
+        };
+        let header = headers
+            .get("x-pingback")
+            .and_then(|value| value.to_str().ok());
+
+        let (source, href) = [
+            ("link[rel=pingback]", link("pingback")),
+            ("link[rel=EditURI]", link("EditURI")),
+            ("X-Pingback header", header),
+        ]
+        .into_iter()
+        .find_map(|(source, href)| Some((source, href?)))?;
+        let mut url = self.base_url.join(href.trim()).ok()?;
+        if url.host_str() != self.base_url.host_str() {
+            return None;
         }
-        None
+        url.set_query(None);
+        url.set_fragment(None);
+        self.record_evidence("XML-RPC endpoint", source, url.as_str());
+        Some(url.to_string())
+    }
+
+    /// Estimate a minimum WordPress version from the REST API routes
+    ///
+    /// Returns `>=X.Y` for the newest release whose routes are present.
+    fn estimate_wp_version(&self, rest_root: Option<&WpJsonResponse>) -> Option<String> {
+        let routes = rest_root?.routes.as_ref()?;
+        let (route, version) = REST_ROUTE_VERSIONS
+            .iter()
+            .find(|(route, _)| routes.keys().any(|key| key.starts_with(route)))?;
+        let estimate = format!(">={}", version);
+        self.record_evidence(
+            format!("WordPress version {} (estimate)", estimate),
+            "REST API routes",
+            *route,
+        );
+        Some(estimate)
+    }
+
+    /// Detect WordPress from `/wp-includes/` asset paths in the page
+    ///
+    /// Emoji, embed, and concatenated scripts load from `wp-includes` even when
+    /// `wp-content` fingerprints are stripped. Only relative and same-host
+    /// URLs count, so links to WordPress documentation or other sites don't.
+    fn detect_wp_from_includes(&self, html: &str) -> Option<()> {
+        self.detect_wp_from_asset_path(html, "wp-includes/", "wp-includes path")
+    }
+
+    /// Detect WordPress from theme and plugin asset paths under `/wp-content/`
+    ///
+    /// Only consulted behind a CDN, where cookie detection is unreliable, or
+    /// to confirm a restricted REST API.
+    /// The same host rules as [`detect_wp_from_includes`](Self::detect_wp_from_includes)
+    /// apply.
+    fn detect_wp_from_content_paths(&self, html: &str) -> Option<()> {
+        self.detect_wp_from_asset_path(html, "wp-content/(?:themes|plugins)/", "wp-content path")
+    }
+
+    /// Find a relative or same-host asset path ending in `dir` (a regex)
+    fn detect_wp_from_asset_path(&self, html: &str, dir: &str, source: &str) -> Option<()> {
+        let re = Regex::new(&format!(r#"["'(=]\s*((?:https?:)?[^"'\s()<>]*?/{})"#, dir)).ok()?;
+        let html = html.replace("\\/", "/");
+        let host = self.base_url.host_str()?;
+
+        let path = re
+            .captures_iter(&html)
+            .filter_map(|caps| caps.get(1))
+            .map(|m| m.as_str())
+            .find(|path| {
+                let absolute = if path.starts_with("//") {
+                    Url::parse(&format!("https:{}", path)).ok()
+                } else if path.starts_with("http") {
+                    Url::parse(path).ok()
+                } else {
+                    return true;
+                };
+                absolute.is_some_and(|url| url.host_str() == Some(host))
+            })?;
+        self.record_evidence("WordPress", source, path);
+        Some(())
     }
 
     /// Detect version from RSS feed
+    #[instrument(level = "debug", skip_all, ret)]
     async fn detect_version_from_feed(&self) -> Option<String> {
-        let feed_url = self.base_url.join(WP_FEED_PATH).ok()?;
+        let feed_url = self.base_url.join(&self.detection_paths.feed).ok()?;
         let html = self.fetch_page(&feed_url).await.ok()?;
 
         // Look for <generator>https://wordpress.org/?v=X.Y.Z</generator>
         let re = Regex::new(r"wordpress\.org/\?v=([0-9.]+)").ok()?;
-        re.captures(&html)?.get(1).map(|m| m.as_str().to_string())
+        let caps = re.captures(&html)?;
+        let version = caps.get(1)?.as_str().to_string();
+        self.record_evidence(
+            format!("WordPress version {}", version),
+            "feed",
+            caps.get(0)?.as_str(),
+        );
+        Some(version)
     }
 
     /// Detect version from readme.html
+    #[instrument(level = "debug", skip_all, ret)]
     async fn detect_version_from_readme(&self) -> Option<String> {
-        let readme_url = self.base_url.join(WP_README_PATH).ok()?;
+        let readme_url = self.base_url.join(&self.detection_paths.readme).ok()?;
         let html = self.fetch_page(&readme_url).await.ok()?;
 
         // Look for "Version X.Y.Z" in readme
         let re = Regex::new(r"Version\s+([0-9.]+)").ok()?;
-        re.captures(&html)?.get(1).map(|m| m.as_str().to_string())
+        let caps = re.captures(&html)?;
+        let version = caps.get(1)?.as_str().to_string();
+        self.record_evidence(
+            format!("WordPress version {}", version),
+            "readme.html",
+            caps.get(0)?.as_str(),
+        );
+        Some(version)
     }
 
-    /// Detect WordPress via wp-json REST API endpoint
-    async fn detect_wp_from_rest_api(&self) -> Option<()> {
-        let api_url = self.base_url.join(WP_JSON_PATH).ok()?;
+    /// Detect version from `?ver=` on core assets (e.g., wp-emoji-release.min.js)
+    ///
+    /// Returns the most common version across all `/wp-includes/` assets,
+    /// ignoring timestamps and hashes.
+    fn detect_version_from_core_assets(&self, document: &Html) -> Option<String> {
+        let (version, count) = Self::core_asset_version(document)?;
+        self.record_evidence(
+            format!("WordPress version {}", version),
+            "core assets",
+            format!("{} /wp-includes/ asset(s) with ver={}", count, version),
+        );
+        Some(version)
+    }
+
+    /// Most common `?ver=` across `/wp-includes/` assets, with its number of uses
+    fn core_asset_version(document: &Html) -> Option<(String, usize)> {
+        let re =
+            Regex::new(r#"/wp-includes/[^'"\s?]+\?(?:[^'"\s]*&(?:amp;)?)?ver=([0-9a-zA-Z._-]+)"#)
+                .ok()?;
+        let html = document.html();
 
-        let response = self.client.get(api_url.as_str()).send().await.ok()?;
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for caps in re.captures_iter(&html) {
+            let version = Self::normalize_version(caps.get(1)?.as_str());
+            if !version.starts_with('(') {
+                *counts.entry(version).or_default() += 1;
+            }
+        }
 
-        if !response.status().is_success() {
+        // Most common version wins; ties go to the lexically greatest for stability
+        counts
+            .into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)))
+    }
+
+    /// Detect version from the generator tag or core assets of `wp-login.php`
+    ///
+    /// Gives up when the login page is behind authentication (401/403) or
+    /// redirects elsewhere, such as to a custom login URL.
+    #[instrument(level = "debug", skip_all, ret)]
+    async fn detect_version_from_login(&self) -> Option<String> {
+        let login_url = self.base_url.join(WP_LOGIN_PATH).ok()?;
+        let page = self.fetch_full_page(&login_url).await.ok()?;
+        if !page.final_url.path().ends_with(WP_LOGIN_PATH) {
+            debug!(final_url = %page.final_url, "login page redirected, skipping");
+            return None;
+        }
+
+        let document = Html::parse_document(&page.html);
+        let (version, detail) = match Self::generator_version(&document) {
+            Some((version, content)) => (version, content),
+            None => {
+                let (version, count) = Self::core_asset_version(&document)?;
+                let detail = format!("{} /wp-includes/ asset(s) with ver={}", count, version);
+                (version, detail)
+            }
+        };
+        self.record_evidence(
+            format!("WordPress version {}", version),
+            "login page",
+            detail,
+        );
+        Some(version)
+    }
+
+    /// Fetch and parse the wp-json REST API root
+    ///
+    /// Returns the REST API status alongside the parsed root, which is only
+    /// present when the API is open. The root advertised in the homepage
+    /// `Link` header is used unless the REST API path was overridden.
+    #[instrument(level = "debug", skip_all, ret)]
+    async fn fetch_rest_root(
+        &self,
+        homepage_headers: &HeaderMap,
+    ) -> (Option<RestApiStatus>, Option<WpJsonResponse>) {
+        let advertised = (self.detection_paths.rest_api == WP_JSON_PATH)
+            .then(|| self.rest_api_link(homepage_headers))
+            .flatten();
+        let api_url = match advertised {
+            Some(url) => url,
+            None => match self.base_url.join(&self.detection_paths.rest_api) {
+                Ok(url) => url,
+                Err(_) => return (None, None),
+            },
+        };
+        let response = match self.send(self.client.get(api_url.as_str())).await {
+            Ok(response) => response,
+            Err(e) => {
+                self.record_failure(&api_url, &e);
+                return (None, None);
+            }
+        };
+
+        match response.status().as_u16() {
+            200..=299 => {}
+            status @ (401 | 403) => {
+                self.record_evidence(
+                    "REST API restricted",
+                    api_url.path(),
+                    format!("status {}", status),
+                );
+                return (Some(RestApiStatus::Restricted), None);
+            }
+            404 | 410 => return (Some(RestApiStatus::Absent), None),
+            status => {
+                self.record_failure(&api_url, &Error::HttpStatus(status));
+                return (None, None);
+            }
+        }
+
+        // Try to parse as WordPress REST API response; soft-404 HTML means no API
+        match response.json::<WpJsonResponse>().await {
+            Ok(root) => (Some(RestApiStatus::Open), Some(root)),
+            Err(_) => (Some(RestApiStatus::Absent), None),
+        }
+    }
+
+    /// REST API root from `Link: <...>; rel="https://api.w.org/"`
+    ///
+    /// Covers installs with a custom REST prefix or plain permalinks
+    /// (`?rest_route=/`). Only same-host URLs are accepted.
+    fn rest_api_link(&self, headers: &HeaderMap) -> Option<Url> {
+        let href = headers
+            .get_all("link")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .find(|entry| entry.contains(&format!("rel=\"{}\"", WP_API_LINK_REL)))
+            .and_then(|entry| {
+                let start = entry.find('<')?;
+                let end = start + entry[start..].find('>')?;
+                Some(entry[start + 1..end].trim())
+            })?;
+        let url = self.base_url.join(href).ok()?;
+        if url.host_str() != self.base_url.host_str() {
             return None;
         }
+        self.record_evidence("REST API root", "Link header", url.as_str());
+        Some(url)
+    }
 
-        // Try to parse as WordPress REST API response
-        let api_response: WpJsonResponse = response.json().await.ok()?;
+    /// Detect WordPress via wp-json REST API endpoint
+    fn detect_wp_from_rest_api(&self, rest_root: Option<&WpJsonResponse>) -> Option<()> {
+        let api_response = rest_root?;
 
         // Check for WordPress-specific namespaces
-        if let Some(namespaces) = &api_response.namespaces
-            && namespaces.iter().any(|ns| ns.starts_with("wp/"))
+        if let Some(namespace) = api_response
+            .namespaces
+            .iter()
+            .flatten()
+            .find(|ns| ns.starts_with("wp/"))
         {
+            self.record_evidence("WordPress", "REST API", format!("namespace {}", namespace));
             return Some(());
         }
 
         // If we got a valid response with expected fields, it's likely WordPress
         if api_response.name.is_some() || api_response.url.is_some() {
+            self.record_evidence("WordPress", "REST API", "site name/url in /wp-json/");
             return Some(());
         }
 
         None
     }
 
-    /// Check for WordPress cookies in response headers
-    async fn detect_wp_from_cookies(&self) -> Option<()> {
-        let response = self.client.get(self.base_url.as_str()).send().await.ok()?;
-
+    /// Check `Set-Cookie` headers for WordPress cookies
+    fn detect_wp_from_set_cookie(&self, headers: &HeaderMap) -> Option<()> {
         // Check for WordPress-specific cookies
-        for cookie in response.cookies() {
-            let name = cookie.name();
+        let names = headers
+            .get_all("set-cookie")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .filter_map(|v| v.split_once('=').map(|(name, _)| name.trim()));
+        for name in names {
             let is_wp_cookie =
                 WP_COOKIE_PREFIXES.iter().any(|p| name.starts_with(p)) || name == WP_LANG_COOKIE;
             if is_wp_cookie {
+                self.record_evidence("WordPress", "cookie", name);
                 return Some(());
             }
         }
 
         // Also check Set-Cookie headers for WordPress patterns
-        if let Some(set_cookie) = response.headers().get("set-cookie")
+        if let Some(set_cookie) = headers.get("set-cookie")
             && let Ok(cookie_str) = set_cookie.to_str()
             && WP_COOKIE_PREFIXES.iter().any(|p| cookie_str.contains(p))
         {
+            let name = cookie_str.split('=').next().unwrap_or(cookie_str);
+            self.record_evidence("WordPress", "Set-Cookie header", name);
             return Some(());
         }
 
         None
     }
 
-    /// Detect the main theme
-    async fn detect_theme(&self, document: &Html) -> Option<ThemeInfo> {
-        // Look for theme in stylesheet URLs
-        let link_selector = Selector::parse("link[rel='stylesheet']").ok()?;
-
-        for element in document.select(&link_selector) {
-            if let Some(href) = element.value().attr("href")
-                && let Some(mut theme) = self.extract_theme_from_url(href)
-            {
-                // Fetch latest version from WordPress.org
-                theme.latest_version = self.fetch_theme_latest_version(&theme.slug).await;
-                return Some(theme);
-            }
+    /// Check response headers for WordPress fingerprints
+    ///
+    /// Looks at the `Link` header (REST API discovery), `X-Pingback` (XML-RPC)
+    /// and `X-Powered-By`. These survive removal of the generator meta tag.
+    fn detect_wp_from_header_map(&self, headers: &HeaderMap) -> Option<()> {
+        // Link: <https://example.com/wp-json/>; rel="https://api.w.org/"
+        let api_link = headers
+            .get_all("link")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .find(|v| v.contains(WP_API_LINK_REL));
+        if let Some(link) = api_link {
+            self.record_evidence("WordPress", "Link header", link);
+            return Some(());
         }
 
-        // Also check style tags and other sources
-        let style_re = Regex::new(r"/wp-content/themes/([^/]+)/").ok()?;
+        // X-Pingback: https://example.com/xmlrpc.php
+        if let Some(pingback) = headers.get("x-pingback")
+            && let Ok(pingback_str) = pingback.to_str()
+            && pingback_str.contains(WP_XMLRPC_PATH)
+        {
+            self.record_evidence("WordPress", "X-Pingback header", pingback_str);
+            return Some(());
+        }
 
-        let html = document.html();
-        if let Some(caps) = style_re.captures(&html) {
-            let slug = caps.get(1)?.as_str().to_string();
-            let latest_version = self.fetch_theme_latest_version(&slug).await;
-            return Some(ThemeInfo {
-                slug,
-                version: None,
-                latest_version,
+        // X-Powered-By: WordPress / WP Engine
+        let powered_by = headers
+            .get_all("x-powered-by")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .find(|v| {
+                let v = v.to_lowercase();
+                WP_POWERED_BY_HINTS.iter().any(|hint| v.contains(hint))
             });
+        if let Some(powered_by) = powered_by {
+            self.record_evidence("WordPress", "X-Powered-By header", powered_by);
+            return Some(());
         }
 
         None
     }
 
-    /// Extract theme info from a URL
-    fn extract_theme_from_url(&self, url: &str) -> Option<ThemeInfo> {
-        // Match /wp-content/themes/theme-name/
-        let re = Regex::new(r"/wp-content/themes/([^/]+)/").ok()?;
-        let caps = re.captures(url)?;
-        let slug = caps.get(1)?.as_str().to_string();
+    /// Extract the PHP version from `X-Powered-By` (e.g., `PHP/8.1.27`)
+    ///
+    /// Hardened servers omit the header or the version (`expose_php = Off`).
+    fn detect_php_version(headers: &HeaderMap) -> Option<String> {
+        let re = Regex::new(r"(?i)\bPHP/([0-9]+\.[0-9]+(?:\.[0-9]+)?)").ok()?;
+        headers
+            .get_all("x-powered-by")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .find_map(|value| Some(re.captures(value)?.get(1)?.as_str().to_string()))
+    }
 
-        // Try to extract version from URL query params
-        let version = if let Some(v_pos) = url.find("ver=") {
-            let v_start = v_pos + 4;
-            let v_end = url[v_start..]
-                .find(|c: char| !c.is_ascii_alphanumeric() && c != '.' && c != '-' && c != '_')
-                .map(|i| v_start + i)
-                .unwrap_or(url.len());
-            let raw_version = url[v_start..v_end].to_string();
-            Some(Self::normalize_version(&raw_version))
-        } else {
-            None
+    /// Recognize a CDN or proxy from [`CDN_SIGNATURES`]
+    ///
+    /// Returns the matching header and the CDN name.
+    fn detect_cdn(headers: &HeaderMap) -> Option<(&'static str, &'static str)> {
+        CDN_SIGNATURES
+            .iter()
+            .find(|(header, needle, _)| {
+                headers.get_all(*header).iter().any(|value| {
+                    value
+                        .to_str()
+                        .is_ok_and(|value| value.to_ascii_lowercase().contains(needle))
+                })
+            })
+            .map(|(header, _, name)| (*header, *name))
+    }
+
+    /// Read the server software from the `Server` header
+    ///
+    /// A blank header counts as masked.
+    fn detect_server(headers: &HeaderMap) -> Option<String> {
+        let server = headers.get("server")?.to_str().ok()?.trim();
+        (!server.is_empty()).then(|| server.to_string())
+    }
+
+    /// Read the site locale from the `wp_lang` cookie, else `<html lang>`
+    ///
+    /// The cookie carries the WordPress locale as is (`de_DE`); the `lang`
+    /// attribute uses a BCP 47 tag (`de-DE`) and is converted to match.
+    /// Values that don't look like a language tag are ignored.
+    #[instrument(level = "debug", skip_all, ret)]
+    fn detect_locale(&self, document: &Html, headers: &HeaderMap) -> Option<String> {
+        let locale_re = Regex::new(r"^[a-zA-Z]{2,3}(?:[_-][a-zA-Z0-9]{2,8}){0,2}$").unwrap();
+
+        let cookie = headers
+            .get_all("set-cookie")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|cookie| cookie.split(';').next()?.split_once('='))
+            .find(|(name, _)| name.trim() == WP_LANG_COOKIE)
+            .map(|(_, value)| ("wp_lang cookie", value.trim().to_string()));
+        let lang = || {
+            let selector = Selector::parse("html[lang]").ok()?;
+            let lang = document.select(&selector).next()?.value().attr("lang")?;
+            Some(("html lang attribute", lang.trim().replace('-', "_")))
         };
 
-        Some(ThemeInfo {
-            slug,
-            version,
-            latest_version: None,
-        })
+        let (source, locale) = cookie
+            .filter(|(_, locale)| locale_re.is_match(locale))
+            .or_else(|| lang().filter(|(_, locale)| locale_re.is_match(locale)))?;
+        self.record_evidence(format!("locale {}", locale), source, &locale);
+        Some(locale)
     }
 
-    /// Detect plugins from the page (includes mu-plugins)
-    async fn detect_plugins(&self, document: &Html) -> Vec<PluginInfo> {
-        let mut plugin_slugs = HashSet::new();
-        let html = document.html();
+    /// Extract target URLs from `Link` response headers
+    ///
+    /// Sites that preload critical assets (`Link: </wp-content/plugins/foo/a.css?ver=1.2>;
+    /// rel=preload; as=style`) may not reference them in the markup at all.
+    fn extract_link_header_urls(headers: &HeaderMap) -> Vec<String> {
+        headers
+            .get_all("link")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(|entry| {
+                let start = entry.find('<')?;
+                let end = start + entry[start..].find('>')?;
+                Some(entry[start + 1..end].trim().to_string())
+            })
+            .collect()
+    }
 
-        // Regex to find plugin paths - includes both plugins and mu-plugins
-        let plugin_re = Regex::new(r"/wp-content/(?:mu-)?plugins/([a-zA-Z0-9_-]+)/").unwrap();
+    /// Detect WooCommerce from REST namespaces or body classes
+    ///
+    /// When found, probes the public Store API to see whether product data is
+    /// exposed without authentication.
+    #[instrument(level = "debug", skip_all, ret)]
+    async fn detect_woocommerce(
+        &self,
+        document: &Html,
+        rest_root: Option<&WpJsonResponse>,
+    ) -> Option<WooCommerceInfo> {
+        let rest_namespace = rest_root
+            .and_then(|root| root.namespaces.as_ref())
+            .and_then(|ns| ns.iter().find(|n| n.starts_with(WC_REST_NAMESPACE_PREFIX)));
 
-        for caps in plugin_re.captures_iter(&html) {
-            if let Some(slug) = caps.get(1) {
-                let slug_str = slug.as_str().to_string();
-                if !SKIP_PLUGIN_SLUGS.contains(&slug_str.as_str()) {
-                    plugin_slugs.insert(slug_str);
-                }
-            }
-        }
+        let body_selector = Selector::parse("body").ok()?;
+        let body_class = document
+            .select(&body_selector)
+            .filter_map(|body| body.value().attr("class"))
+            .flat_map(str::split_whitespace)
+            .find(|c| c.starts_with(WC_PLUGIN_SLUG));
 
-        // Convert to PluginInfo, fetching latest versions
-        let mut plugins = Vec::new();
-        for slug in plugin_slugs {
-            let version = self.find_plugin_version(&html, &slug);
-            let latest_version = self.fetch_plugin_latest_version(&slug).await;
-            plugins.push(PluginInfo {
-                slug,
-                version,
-                latest_version,
-            });
+        match (rest_namespace, body_class) {
+            (Some(namespace), _) => self.record_evidence(
+                "WooCommerce",
+                "REST API",
+                format!("namespace {}", namespace),
+            ),
+            (None, Some(class)) => self.record_evidence("WooCommerce", "body class", class),
+            (None, None) => return None,
         }
-        plugins
+
+        Some(WooCommerceInfo {
+            store_api_public: self.probe_wc_store_api().await,
+        })
     }
 
-    /// Find plugin version from HTML
-    fn find_plugin_version(&self, html: &str, slug: &str) -> Option<String> {
-        // Look for ver= parameter in plugin URLs (supports both plugins and mu-plugins)
-        let pattern = format!(
-            r#"/wp-content/(?:mu-)?plugins/{}/[^'"]*\?[^'"]*ver=([0-9a-zA-Z._-]+)"#,
-            regex::escape(slug)
-        );
-        let re = Regex::new(&pattern).ok()?;
-        let caps = re.captures(html)?;
-        let version = caps.get(1)?.as_str().to_string();
+    /// Check whether the WooCommerce Store API lists products publicly
+    async fn probe_wc_store_api(&self) -> bool {
+        let Ok(store_url) = self.base_url.join(WC_STORE_API_PATH) else {
+            return false;
+        };
+        let Ok(response) = self.send(self.client.get(store_url.as_str())).await else {
+            return false;
+        };
+        if !response.status().is_success() {
+            return false;
+        }
 
-        // Filter out Unix timestamps (10-digit numbers) and hash-like versions
-        Some(Self::normalize_version(&version))
+        // Product listing is a JSON array
+        response
+            .json::<serde_json::Value>()
+            .await
+            .is_ok_and(|body| body.is_array())
     }
 
-    /// Normalize version string - detect timestamps and hashes
-    fn normalize_version(version: &str) -> String {
-        // Unix timestamp detection (10 digits, starts with 1 or 2, reasonable range)
-        if version.len() == 10
-            && version.chars().all(|c| c.is_ascii_digit())
-            && version.starts_with(['1', '2'])
-        {
-            return format!("(timestamp:{})", version);
+    /// Detect a multisite network via `wp-signup.php` and the REST root
+    ///
+    /// Single-site installs redirect `wp-signup.php` to `wp-login.php`, while
+    /// multisite serves the signup form itself. A namespace from
+    /// [`MULTISITE_REST_NAMESPACES`] in the REST root also marks a network.
+    /// Anything else, or signals that disagree, are treated as ambiguous.
+    #[instrument(level = "debug", skip_all, ret)]
+    async fn detect_multisite(&self, rest_root: Option<&WpJsonResponse>) -> Option<bool> {
+        let rest_namespace = rest_root
+            .and_then(|root| root.namespaces.as_ref())
+            .and_then(|namespaces| {
+                namespaces
+                    .iter()
+                    .find(|ns| MULTISITE_REST_NAMESPACES.contains(&ns.as_str()))
+            });
+        let signup = self.detect_multisite_signup().await;
+
+        match (signup, rest_namespace) {
+            (Some(false), Some(_)) => None,
+            (Some(false), None) => Some(false),
+            (_, Some(namespace)) => {
+                self.record_evidence(
+                    "multisite network",
+                    "REST API",
+                    format!("namespace {}", namespace),
+                );
+                Some(true)
+            }
+            (signup, None) => signup,
         }
+    }
 
-        // Git commit hash detection (40 hex chars or 7+ hex abbreviation)
-        if (version.len() == 40 || version.len() >= 7)
-            && version.chars().all(|c| c.is_ascii_hexdigit())
-            && !version.chars().all(|c| c.is_ascii_digit())
+    /// Check whether `wp-signup.php` serves the network signup form
+    async fn detect_multisite_signup(&self) -> Option<bool> {
+        let signup_url = self.base_url.join(WP_SIGNUP_PATH).ok()?;
+        let page = self.fetch_full_page(&signup_url).await.ok()?;
+
+        let path = page.final_url.path();
+        if path.ends_with(WP_LOGIN_PATH) {
+            return Some(false);
+        }
+        // A themed 404 or catch-all page answering 200 is not the signup form
+        if path.ends_with(WP_SIGNUP_PATH)
+            && MULTISITE_SIGNUP_MARKERS
+                .iter()
+                .any(|marker| page.html.contains(marker))
         {
-            let short = if version.len() > 7 {
-                &version[..7]
-            } else {
-                version
-            };
-            return format!("(hash:{})", short);
+            self.record_evidence("multisite network", "HTTP probe", signup_url.as_str());
+            return Some(true);
+        }
+        None
+    }
+
+    /// Check whether the default login page is publicly reachable
+    ///
+    /// Probes `wp-login.php` and `wp-admin/`. A login form served from
+    /// `wp-login.php` counts as accessible; 401/403 (Basic Auth, WAF) and
+    /// 404 (custom login URL) count as protected.
+    #[instrument(level = "debug", skip_all, ret)]
+    async fn detect_login_page(&self) -> Option<bool> {
+        let mut result = None;
+        for path in [WP_LOGIN_PATH, WP_ADMIN_PATH] {
+            match self.probe_login_path(path).await {
+                Some(true) => return Some(true),
+                Some(false) => result = Some(false),
+                None => {}
+            }
+        }
+        result
+    }
+
+    /// Probe a single login-related path
+    async fn probe_login_path(&self, path: &str) -> Option<bool> {
+        let url = self.base_url.join(path).ok()?;
+        let response = self.send(self.client.get(url.as_str())).await.ok()?;
+
+        match response.status().as_u16() {
+            200..=299 if response.url().path().ends_with(WP_LOGIN_PATH) => {
+                self.record_evidence(
+                    "login page accessible",
+                    "HTTP probe",
+                    response.url().as_str(),
+                );
+                Some(true)
+            }
+            200..=299 | 401 | 403 | 404 => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Check whether the login page was moved to a custom URL
+    ///
+    /// Security plugins serve the login form from a secret slug instead of
+    /// `wp-login.php`. The location is taken from a login link on the
+    /// homepage, else from where `wp-admin/` redirects anonymous visitors,
+    /// provided that page serves the login form. Returns whether the login
+    /// was relocated and, if found, its URL.
+    #[instrument(level = "debug", skip_all, ret)]
+    async fn detect_login_relocation(
+        &self,
+        document: &Html,
+        login_accessible: Option<bool>,
+    ) -> (Option<bool>, Option<String>) {
+        if login_accessible == Some(true) {
+            return (Some(false), None);
+        }
+        if let Some(link) = self.extract_login_link(document) {
+            return self.login_location(link, "homepage link");
+        }
+
+        let Ok(admin_url) = self.base_url.join(WP_ADMIN_PATH) else {
+            return (None, None);
+        };
+        let Ok(page) = self.fetch_full_page(&admin_url).await else {
+            return (None, None);
+        };
+        let target = page.final_url;
+        // No redirect, a redirect off-site, or a bounce to the homepage says nothing
+        if target == admin_url
+            || target.host_str() != admin_url.host_str()
+            || target.path() == self.base_url.path()
+        {
+            return (None, None);
+        }
+        // A 2xx error page or a redirect to a shop account page isn't the login
+        if !["loginform", "user_login"]
+            .iter()
+            .any(|marker| page.html.contains(marker))
+        {
+            return (None, None);
+        }
+        self.login_location(target, "wp-admin/ redirect")
+    }
+
+    /// Classify a discovered login URL as the default or a relocated one
+    fn login_location(&self, mut url: Url, source: &str) -> (Option<bool>, Option<String>) {
+        if url.path().ends_with(WP_LOGIN_PATH) {
+            return (Some(false), None);
+        }
+        url.set_query(None);
+        url.set_fragment(None);
+        self.record_evidence("login page relocated", source, url.as_str());
+        (Some(true), Some(url.to_string()))
+    }
+
+    /// Find a same-host login link on the homepage
+    ///
+    /// Checks, in order: the Login/out block, the "Log in" entry of the Meta
+    /// widget, "Log in" links whose query starts with `redirect_to` (as
+    /// `wp_login_url()` builds them), and plain `wp-login.php` links.
+    fn extract_login_link(&self, document: &Html) -> Option<Url> {
+        let same_host = |href: &str| {
+            self.base_url
+                .join(href)
+                .ok()
+                .filter(|url| url.host_str() == self.base_url.host_str())
+        };
+        let candidates = [
+            (".wp-block-loginout a[href]", false),
+            (".widget_meta a[href]", true),
+            ("a[href*='?redirect_to=']", true),
+            ("a[href*='wp-login.php']", false),
+        ];
+        candidates.into_iter().find_map(|(selector, log_in_text)| {
+            let selector = Selector::parse(selector).ok()?;
+            document
+                .select(&selector)
+                .filter(|link| {
+                    !log_in_text
+                        || matches!(
+                            link.text()
+                                .collect::<String>()
+                                .trim()
+                                .to_ascii_lowercase()
+                                .as_str(),
+                            "log in" | "login" | "sign in"
+                        )
+                })
+                .filter_map(|link| link.value().attr("href"))
+                .filter(|href| !href.contains("action=logout"))
+                .find_map(same_host)
+        })
+    }
+
+    /// Check whether `wp-content/debug.log` is world-readable
+    ///
+    /// Only the first kilobyte is requested since the log can be very large.
+    #[instrument(level = "debug", skip_all, ret)]
+    async fn detect_debug_log(&self) -> Option<bool> {
+        let log_url = self.base_url.join(WP_DEBUG_LOG_PATH).ok()?;
+        let response = self
+            .send(
+                self.client
+                    .get(log_url.as_str())
+                    .header("range", PROBE_RANGE),
+            )
+            .await
+            .ok()?;
+
+        if !response.status().is_success() {
+            return Some(false);
+        }
+
+        // Soft-404 pages return HTML with a 200 status; a real log is plain text
+        let is_text = response
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .is_none_or(|ct| ct.starts_with("text/") && !ct.starts_with("text/html"));
+        if is_text {
+            self.record_evidence("debug.log exposed", "HTTP probe", log_url.as_str());
+        }
+
+        Some(is_text)
+    }
+
+    /// Probe the configured backup file names, returning those that are served
+    ///
+    /// Uses HEAD requests, at most `concurrency` at a time. Only a direct 200
+    /// counts: redirects elsewhere and HTML soft-404 pages are ignored.
+    #[instrument(level = "debug", skip_all, ret)]
+    async fn detect_exposed_backups(&self) -> Vec<String> {
+        stream::iter(&self.backup_files)
+            .map(|name| async move {
+                let url = self.base_url.join(name).ok()?;
+                let response = self.send(self.client.head(url.as_str())).await.ok()?;
+                let is_html = response
+                    .headers()
+                    .get("content-type")
+                    .and_then(|v| v.to_str().ok())
+                    .is_some_and(|ct| ct.starts_with("text/html"));
+                if response.status().as_u16() != 200 || response.url() != &url || is_html {
+                    return None;
+                }
+                self.record_evidence(
+                    format!("backup file {} exposed", name),
+                    "HTTP probe",
+                    url.as_str(),
+                );
+                Some(name.clone())
+            })
+            .buffered(self.concurrency)
+            .filter_map(|name| async move { name })
+            .collect()
+            .await
+    }
+
+    /// Check whether `wp-cron.php` can be triggered from outside
+    ///
+    /// Every request runs due cron jobs, so an open endpoint can be abused to
+    /// load the server. A HEAD request keeps the probe cheap; 401/403/404
+    /// means the host blocks it. A 2xx only counts when a random `.php` path
+    /// is not also answered with 2xx, as catch-all servers do.
+    #[instrument(level = "debug", skip_all, ret)]
+    async fn detect_wp_cron(&self) -> Option<bool> {
+        let cron_url = self.base_url.join(WP_CRON_PATH).ok()?;
+        let response = self.head_cron_probe(&cron_url).await?;
+
+        match response.status().as_u16() {
+            200..=299 if response.url().path().ends_with(WP_CRON_PATH) => {
+                let control_url = self
+                    .base_url
+                    .join(&format!("{}.php", Self::random_slug()))
+                    .ok()?;
+                let control = self.head_cron_probe(&control_url).await?;
+                if control.status().is_success() {
+                    return None;
+                }
+                self.record_evidence("wp-cron.php exposed", "HTTP probe", cron_url.as_str());
+                Some(true)
+            }
+            200..=299 | 401 | 403 | 404 | 410 => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Send a HEAD request with the short wp-cron probe timeout
+    async fn head_cron_probe(&self, url: &Url) -> Option<reqwest::Response> {
+        self.send(
+            self.client
+                .head(url.as_str())
+                .timeout(Duration::from_secs(CRON_PROBE_TIMEOUT_SECS)),
+        )
+        .await
+        .ok()
+    }
+
+    /// Check whether the theme editor is blocked instead of guarded by login
+    ///
+    /// Unauthenticated requests normally redirect to `wp-login.php` whether or
+    /// not file editing is disabled, so only an outright 403/404/410 counts as
+    /// blocked. Anything else is inconclusive.
+    #[instrument(level = "debug", skip_all, ret)]
+    async fn detect_file_editor_blocked(&self) -> Option<bool> {
+        let url = self.base_url.join(WP_THEME_EDITOR_PATH).ok()?;
+        let response = self.send(self.client.get(url.as_str())).await.ok()?;
+
+        match response.status().as_u16() {
+            403 | 404 | 410 => {
+                self.record_evidence(
+                    "theme editor blocked",
+                    "HTTP probe",
+                    format!("{} ({})", url, response.status().as_u16()),
+                );
+                Some(true)
+            }
+            200..=299 if response.url().path().ends_with(WP_LOGIN_PATH) => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Detect the main theme
+    #[instrument(level = "debug", skip_all, ret)]
+    async fn detect_theme(&self, document: &Html, preload_urls: &[String]) -> Option<ThemeInfo> {
+        // Look for theme in stylesheet URLs, then in preload `Link` headers
+        let link_selector = Selector::parse("link[rel='stylesheet']").ok()?;
+        let stylesheet_urls = document
+            .select(&link_selector)
+            .filter_map(|element| element.value().attr("href"))
+            .map(|href| ("stylesheet link", href));
+        let header_urls = preload_urls.iter().map(|url| ("Link header", url.as_str()));
+
+        for (source, href) in stylesheet_urls.chain(header_urls) {
+            if let Some(mut theme) = self.extract_theme_from_url(href) {
+                self.record_evidence(format!("theme {}", theme.slug), source, href);
+                theme.disputed_version = self.detect_theme_version_drift(&theme).await;
+                // Fetch latest version from WordPress.org
+                self.lookup_theme(&mut theme).await;
+                return Some(theme);
+            }
+        }
+
+        // Also check style tags and other sources (including JSON-escaped URLs)
+        let style_re = Regex::new(r#"\\?/wp-content\\?/themes\\?/([^/\\'"]+)\\?/"#).ok()?;
+
+        let html = document.html();
+        if let Some(caps) = style_re.captures(&html) {
+            let slug = caps.get(1)?.as_str().to_string();
+            self.record_evidence(
+                format!("theme {}", slug),
+                "page markup",
+                caps.get(0)?.as_str(),
+            );
+            let mut theme = ThemeInfo {
+                slug,
+                version: None,
+                latest_version: None,
+                disputed_version: None,
+                last_updated: None,
+                tested_up_to: None,
+            };
+            self.lookup_theme(&mut theme).await;
+            return Some(theme);
+        }
+
+        None
+    }
+
+    /// Check whether the homepage is a maintenance or "coming soon" splash page
+    ///
+    /// Core's maintenance message counts alone; a splash plugin's assets need
+    /// splash page wording too. Returns None for an empty homepage (not
+    /// fetched), since there is no page to judge.
+    #[instrument(level = "debug", skip_all, ret)]
+    fn detect_maintenance_mode(&self, html: &str) -> Option<bool> {
+        if html.is_empty() {
+            return None;
+        }
+        let html = html.to_lowercase();
+        if html.contains(CORE_MAINTENANCE_MESSAGE) {
+            self.record_evidence("maintenance mode", "page markup", CORE_MAINTENANCE_MESSAGE);
+            return Some(true);
+        }
+        let asset = MAINTENANCE_PLUGIN_ASSETS
+            .iter()
+            .find(|asset| html.contains(*asset));
+        let text = MAINTENANCE_PAGE_TEXT
+            .iter()
+            .find(|text| html.contains(*text));
+        if let (Some(asset), Some(text)) = (asset, text) {
+            self.record_evidence(
+                "maintenance mode",
+                "page markup",
+                format!("{} with \"{}\"", asset, text),
+            );
+            return Some(true);
+        }
+        Some(false)
+    }
+
+    /// Detect a known page builder from body classes, generator tags, or asset paths
+    #[instrument(level = "debug", skip_all, ret)]
+    fn detect_page_builder(&self, document: &Html, html: &str) -> Option<PageBuilderInfo> {
+        let body_selector = Selector::parse("body").ok()?;
+        let body_classes: Vec<&str> = document
+            .select(&body_selector)
+            .flat_map(|body| body.value().classes())
+            .collect();
+        let generator_selector = Selector::parse("meta[name='generator']").ok()?;
+        let generators: Vec<&str> = document
+            .select(&generator_selector)
+            .filter_map(|element| element.value().attr("content"))
+            .collect();
+        let version_re = Regex::new(r"\d+(?:\.\d+)+").ok()?;
+
+        for builder in PAGE_BUILDERS {
+            if let Some(content) = generators.iter().find(|content| {
+                builder
+                    .generators
+                    .iter()
+                    .any(|prefix| content.starts_with(prefix))
+            }) {
+                self.record_evidence(
+                    format!("page builder {}", builder.name),
+                    "meta[name=generator]",
+                    *content,
+                );
+                return Some(PageBuilderInfo {
+                    name: builder.name.to_string(),
+                    version: version_re.find(content).map(|m| m.as_str().to_string()),
+                });
+            }
+
+            let marker = body_classes
+                .iter()
+                .find(|class| builder.body_classes.contains(class))
+                .map(|class| ("body class", *class))
+                .or_else(|| {
+                    builder
+                        .asset_paths
+                        .iter()
+                        .find(|path| html.contains(*path))
+                        .map(|path| ("asset path", *path))
+                });
+            if let Some((source, detail)) = marker {
+                self.record_evidence(format!("page builder {}", builder.name), source, detail);
+                return Some(PageBuilderInfo {
+                    name: builder.name.to_string(),
+                    version: None,
+                });
+            }
+        }
+
+        None
+    }
+
+    /// Extract theme info from a URL
+    fn extract_theme_from_url(&self, url: &str) -> Option<ThemeInfo> {
+        // Match /wp-content/themes/theme-name/
+        let re = Regex::new(r"/wp-content/themes/([^/]+)/").ok()?;
+        let caps = re.captures(url)?;
+        let slug = caps.get(1)?.as_str().to_string();
+
+        // Try to extract version from URL query params
+        let version = url
+            .split_once('?')
+            .and_then(|(_, query)| Self::extract_version_param(query))
+            .map(Self::normalize_version);
+
+        Some(ThemeInfo {
+            slug,
+            version,
+            latest_version: None,
+            disputed_version: None,
+            last_updated: None,
+            tested_up_to: None,
+        })
+    }
+
+    /// Compare the enqueued theme version with the `Version:` in `style.css`
+    ///
+    /// Caching and optimization plugins often rewrite `?ver=` into a
+    /// cache-buster, so a mismatch means the reported version is unreliable.
+    /// Returns the `style.css` version when both are known and disagree.
+    #[instrument(level = "debug", skip_all, fields(slug = %theme.slug), ret)]
+    async fn detect_theme_version_drift(&self, theme: &ThemeInfo) -> Option<String> {
+        let version = theme.version.as_deref()?;
+        let url = self
+            .base_url
+            .join(&format!("wp-content/themes/{}/style.css", theme.slug))
+            .ok()?;
+        let body = self.fetch_leading_bytes(&url).await?;
+        let style_version = Self::extract_header_comment_version(&String::from_utf8_lossy(&body))?;
+        if Self::same_version(version, &style_version) {
+            return None;
+        }
+
+        self.record_evidence(
+            format!(
+                "theme {} version {} disputed by style.css version {}",
+                theme.slug, version, style_version
+            ),
+            "style.css header",
+            url.as_str(),
+        );
+        Some(style_version)
+    }
+
+    /// Check if two versions are equal, ignoring trailing `.0` parts (`4.6` = `4.6.0`)
+    fn same_version(a: &str, b: &str) -> bool {
+        let trim = |v: &str| v.trim_end_matches(".0").to_string();
+        trim(a) == trim(b)
+    }
+
+    /// Detect plugins from the page (includes mu-plugins)
+    #[instrument(level = "debug", skip_all)]
+    async fn detect_plugins(
+        &self,
+        document: &Html,
+        preload_urls: &[String],
+        rest_namespaces: &[String],
+        theme: Option<&ThemeInfo>,
+    ) -> Vec<PluginInfo> {
+        // Header-only asset references are matched (and versioned) like page markup
+        let mut html = document.html();
+        let page_slugs = Self::extract_plugin_slugs(&html);
+        let json_ld = Self::extract_json_ld_plugins(document);
+        for url in preload_urls {
+            html.push('\n');
+            html.push_str(url);
+        }
+
+        // Remember where each slug was first seen, for evidence
+        let mut sources: HashMap<String, &str> = HashMap::new();
+        let found = [
+            ("page markup", page_slugs),
+            (
+                "Link header",
+                Self::extract_plugin_slugs(&preload_urls.join("\n")),
+            ),
+            (
+                "inline script",
+                Self::extract_inline_script_plugin_slugs(document),
+            ),
+            (
+                "REST namespace",
+                Self::extract_rest_namespace_plugins(rest_namespaces),
+            ),
+            ("JSON-LD", json_ld.keys().cloned().collect()),
+            ("AJAX action", Self::extract_ajax_action_plugins(document)),
+        ];
+        for (source, slugs) in found {
+            for slug in slugs {
+                sources.entry(slug).or_insert(source);
+            }
+        }
+        // Drop modules bundled with the theme or a page builder
+        let theme_bundled = theme
+            .map(|theme| Self::extract_theme_bundled_slugs(&html, &theme.slug))
+            .unwrap_or_default();
+        debug!(slugs = ?theme_bundled, "theme-bundled slugs ignored");
+        sources.retain(|slug, _| {
+            !self.skip_plugins.contains(slug)
+                && !BUNDLED_PLUGIN_SLUGS.contains(&slug.as_str())
+                && !theme_bundled.contains(slug)
+        });
+        let mut plugin_slugs: HashSet<String> = sources.keys().cloned().collect();
+        debug!(slugs = ?plugin_slugs, "plugin slugs found in page");
+
+        // Probe wordlist candidates, names guessed from localized script
+        // objects, and unknown REST namespaces that weren't referenced in the page
+        let localized = Self::extract_localized_plugin_candidates(document);
+        trace!(candidates = ?localized, "localized script candidates");
+        let namespaced = Self::rest_namespace_candidates(rest_namespaces);
+        trace!(candidates = ?namespaced, "REST namespace candidates");
+        let candidates: HashSet<&str> = self
+            .plugin_wordlist
+            .iter()
+            .map(String::as_str)
+            .chain(localized.iter().map(String::as_str))
+            .chain(namespaced.iter().map(String::as_str))
+            .filter(|slug| {
+                Self::is_valid_slug(slug)
+                    && !plugin_slugs.contains(*slug)
+                    && !self.skip_plugins.contains(*slug)
+            })
+            .collect();
+        let candidates: Vec<&str> = candidates.into_iter().collect();
+        for slug in self.probe_plugin_slugs(candidates).await {
+            sources.insert(slug.clone(), "HEAD probe");
+            plugin_slugs.insert(slug);
+        }
+
+        // Convert to PluginInfo, fetching latest versions
+        let mut plugins = Vec::new();
+        for slug in plugin_slugs {
+            self.progress(|| ScanProgress::CheckingPlugin(slug.clone()));
+            // An asset's ?ver= beats a version the generator string claims
+            let json_ld_version = json_ld.get(&slug).cloned().flatten();
+            let version = match self.find_plugin_version(&html, &slug) {
+                Some(version) => Some(version),
+                None => match json_ld_version {
+                    Some(version) => {
+                        self.record_evidence(
+                            format!("plugin {} version {}", slug, version),
+                            "JSON-LD",
+                            "application/ld+json",
+                        );
+                        Some(version)
+                    }
+                    None => self.detect_plugin_version_from_asset(&html, &slug).await,
+                },
+            };
+            debug!(slug, version = ?version, "plugin detected");
+            self.record_evidence(
+                format!("plugin {}", slug),
+                sources.get(&slug).copied().unwrap_or("page markup"),
+                format!("wp-content/plugins/{}/", slug),
+            );
+            plugins.push(self.lookup_plugin(slug, version).await);
+        }
+        plugins
+    }
+
+    /// Extract plugin slugs from plugin paths in the HTML
+    ///
+    /// Matches relative and absolute URLs (including CDN hosts) as well as
+    /// JSON-escaped forms like `https:\/\/cdn.example.com\/wp-content\/plugins\/...`.
+    fn extract_plugin_slugs(html: &str) -> HashSet<String> {
+        // Regex to find plugin paths - includes both plugins and mu-plugins
+        let plugin_re =
+            Regex::new(r"\\?/wp-content\\?/(?:mu-)?plugins\\?/([a-zA-Z0-9_-]+)\\?/").unwrap();
+
+        plugin_re
+            .captures_iter(html)
+            .filter_map(|caps| caps.get(1))
+            .map(|slug| slug.as_str())
+            .filter(|slug| !SKIP_PLUGIN_SLUGS.contains(slug))
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Extract known plugins, with versions where given, from JSON-LD blocks
+    ///
+    /// Malformed blocks are skipped.
+    fn extract_json_ld_plugins(document: &Html) -> HashMap<String, Option<String>> {
+        let selector = Selector::parse(r#"script[type="application/ld+json"]"#).unwrap();
+        let mut found = HashMap::new();
+        for script in document.select(&selector) {
+            let text: String = script.text().collect();
+            match serde_json::from_str::<serde_json::Value>(text.trim()) {
+                Ok(value) => Self::collect_json_ld_plugins(&value, &mut found),
+                Err(e) => debug!(error = %e, "skipping malformed JSON-LD block"),
+            }
+        }
+        found
+    }
+
+    /// Walk a JSON-LD value for `generator` strings
+    fn collect_json_ld_plugins(
+        value: &serde_json::Value,
+        found: &mut HashMap<String, Option<String>>,
+    ) {
+        use serde_json::Value;
+
+        match value {
+            Value::Array(items) => {
+                for item in items {
+                    Self::collect_json_ld_plugins(item, found);
+                }
+            }
+            Value::Object(map) => {
+                if let Some(Value::String(generator)) = map.get("generator") {
+                    Self::match_json_ld_plugin(generator, found);
+                }
+                for child in map.values() {
+                    Self::collect_json_ld_plugins(child, found);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Record a JSON-LD generator if it belongs to a known plugin
+    ///
+    /// A version trailing the name (`Yoast SEO 21.7`) is kept. A versioned
+    /// match wins over an unversioned one.
+    fn match_json_ld_plugin(name: &str, found: &mut HashMap<String, Option<String>>) {
+        let Some((prefix, slug)) = JSON_LD_PLUGINS.iter().find(|(prefix, _)| {
+            name.get(..prefix.len())
+                .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+        }) else {
+            return;
+        };
+        let version = Regex::new(r"\d+(?:\.\d+)+")
+            .ok()
+            .and_then(|re| Some(re.find(&name[prefix.len()..])?.as_str().to_string()))
+            .map(|version| Self::normalize_version(&version));
+        let entry = found.entry(slug.to_string()).or_default();
+        if entry.is_none() {
+            *entry = version;
+        }
+    }
+
+    /// Map REST API namespaces to the plugins that register them
+    ///
+    /// Matching is by prefix (`wpforms/v1` -> `wpforms`), see
+    /// [`REST_NAMESPACE_PLUGINS`].
+    fn extract_rest_namespace_plugins(namespaces: &[String]) -> HashSet<String> {
+        namespaces
+            .iter()
+            .filter_map(|namespace| {
+                REST_NAMESPACE_PLUGINS
+                    .iter()
+                    .find(|(prefix, _)| namespace.starts_with(prefix))
+                    .map(|(_, slug)| slug.to_string())
+            })
+            .collect()
+    }
+
+    /// Map `admin-ajax.php` actions in inline scripts to the plugins that register them
+    ///
+    /// Matches `action=foo` query strings as well as `action: 'foo'` and
+    /// `"action":"foo"` object fields, then looks the value up by prefix in
+    /// [`AJAX_ACTION_PLUGINS`].
+    fn extract_ajax_action_plugins(document: &Html) -> HashSet<String> {
+        let Ok(selector) = Selector::parse("script:not([src])") else {
+            return HashSet::new();
+        };
+        let action_re = Regex::new(r#"\baction["']?\s*[:=]\s*["']?([a-zA-Z0-9_-]+)"#).unwrap();
+
+        document
+            .select(&selector)
+            .flat_map(|script| {
+                let text = script.text().collect::<String>();
+                action_re
+                    .captures_iter(&text)
+                    .filter_map(|caps| caps.get(1))
+                    .filter_map(|action| {
+                        AJAX_ACTION_PLUGINS
+                            .iter()
+                            .find(|(prefix, _)| action.as_str().starts_with(prefix))
+                            .map(|(_, slug)| slug.to_string())
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Namespaces of the routes listed in the REST API index
+    ///
+    /// A route key like `/tribe/events/v1/events/(?P<id>\d+)` yields
+    /// `tribe/events/v1`: segments up to and including the first version
+    /// segment (`v1`, `1.0`), or just the first segment when there is none.
+    /// Empty when the index omits routes, as sites that filter `rest_index`
+    /// or restrict the REST API do.
+    fn route_namespaces(rest_root: Option<&WpJsonResponse>) -> Vec<String> {
+        let Some(routes) = rest_root.and_then(|root| root.routes.as_ref()) else {
+            return Vec::new();
+        };
+        let version_re = Regex::new(r"^(?:v\d+(?:\.\d+)*|\d+(?:\.\d+)+)$").unwrap();
+
+        let namespaces: BTreeSet<String> = routes
+            .keys()
+            .filter_map(|route| {
+                let segments: Vec<&str> = route
+                    .trim_start_matches('/')
+                    .split('/')
+                    .take_while(|segment| Self::is_valid_slug(&segment.replace('.', "")))
+                    .collect();
+                let end = segments
+                    .iter()
+                    .position(|segment| version_re.is_match(segment))
+                    .map_or(1, |i| i + 1);
+                (!segments.is_empty()).then(|| segments[..end.min(segments.len())].join("/"))
+            })
+            .collect();
+        namespaces.into_iter().collect()
+    }
+
+    /// Plugin slugs to probe for REST namespaces no table entry covers
+    ///
+    /// Plugins usually name their namespace after their slug (`my-plugin/v1`),
+    /// so the first segment is a good guess. Namespaces mapped by
+    /// [`REST_NAMESPACE_PLUGINS`] and those of core ([`CORE_REST_NAMESPACES`])
+    /// are skipped. Guesses must be confirmed with a probe.
+    fn rest_namespace_candidates(namespaces: &[String]) -> HashSet<String> {
+        namespaces
+            .iter()
+            .filter(|namespace| {
+                !REST_NAMESPACE_PLUGINS
+                    .iter()
+                    .any(|(prefix, _)| namespace.starts_with(prefix))
+            })
+            .filter_map(|namespace| namespace.split('/').next())
+            .filter(|root| Self::is_valid_slug(root) && !CORE_REST_NAMESPACES.contains(root))
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Find plugin slugs that only appear inside the theme's own directory
+    ///
+    /// Themes that bundle plugins sometimes serve them from a nested path
+    /// (`/wp-content/themes/{theme}/inc/wp-content/plugins/{slug}/`), which
+    /// [`Self::extract_plugin_slugs`] also matches. A slug is only treated as
+    /// bundled when every reference to it sits under the theme directory, so
+    /// a plugin that is also installed at the top level is still reported.
+    fn extract_theme_bundled_slugs(html: &str, theme_slug: &str) -> HashSet<String> {
+        let plugin_re = Regex::new(
+            r#"([^"'\s<>()]*?)\\?/wp-content\\?/(?:mu-)?plugins\\?/([a-zA-Z0-9_-]+)\\?/"#,
+        )
+        .unwrap();
+        let theme_dir = format!("/themes/{}/", theme_slug);
+
+        let mut in_theme: HashMap<String, bool> = HashMap::new();
+        for caps in plugin_re.captures_iter(html) {
+            let prefix = caps[1].replace('\\', "");
+            let bundled = prefix.contains(&theme_dir);
+            *in_theme.entry(caps[2].to_string()).or_insert(true) &= bundled;
+        }
+
+        in_theme
+            .into_iter()
+            .filter_map(|(slug, bundled)| bundled.then_some(slug))
+            .collect()
+    }
+
+    /// Extract plugin slugs from plugin base URLs inside inline scripts
+    ///
+    /// Localized config objects often carry a plugin's base URL without a
+    /// trailing slash (`"pluginUrl":"https:\/\/example.com\/wp-content\/plugins\/foo"`),
+    /// which [`Self::extract_plugin_slugs`] does not match.
+    fn extract_inline_script_plugin_slugs(document: &Html) -> HashSet<String> {
+        let Ok(selector) = Selector::parse("script:not([src])") else {
+            return HashSet::new();
+        };
+        let plugin_re =
+            Regex::new(r#"\\?/wp-content\\?/(?:mu-)?plugins\\?/([a-zA-Z0-9_-]+)(?:\\?/|["'])"#)
+                .unwrap();
+
+        document
+            .select(&selector)
+            .flat_map(|script| {
+                let text = script.text().collect::<String>();
+                plugin_re
+                    .captures_iter(&text)
+                    .filter_map(|caps| caps.get(1))
+                    .map(|slug| slug.as_str().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|slug| !SKIP_PLUGIN_SLUGS.contains(&slug.as_str()))
+            .collect()
+    }
+
+    /// Guess plugin slugs from `wp_localize_script` output
+    ///
+    /// WordPress prints localized data as `<script id="{handle}-js-extra">var
+    /// {name} = {...};</script>`, and plugins tend to name both the handle and
+    /// the object after themselves (`myplugin_vars`, `myplugin_ajax`). Objects
+    /// outside a `-js-extra` block are only considered when they look like an
+    /// AJAX config (`ajaxurl`/`ajax_url` or a nonce). The names are guesses,
+    /// so callers must confirm them before reporting a plugin.
+    fn extract_localized_plugin_candidates(document: &Html) -> HashSet<String> {
+        let Ok(selector) = Selector::parse("script:not([src])") else {
+            return HashSet::new();
+        };
+        let var_re = Regex::new(r"\bvar\s+([A-Za-z_][A-Za-z0-9_]*)\s*=\s*\{").unwrap();
+
+        let mut candidates = HashSet::new();
+        for script in document.select(&selector) {
+            let text = script.text().collect::<String>();
+            let handle = script
+                .value()
+                .id()
+                .and_then(|id| id.strip_suffix("-js-extra"));
+            let lower = text.to_ascii_lowercase();
+            let is_ajax_config = ["ajaxurl", "ajax_url", "nonce"]
+                .iter()
+                .any(|marker| lower.contains(marker));
+            if handle.is_none() && !is_ajax_config {
+                continue;
+            }
+
+            candidates.extend(handle.map(Self::localized_name_to_slug));
+            candidates.extend(
+                var_re
+                    .captures_iter(&text)
+                    .filter_map(|caps| caps.get(1))
+                    .map(|name| Self::localized_name_to_slug(name.as_str())),
+            );
+        }
+
+        candidates.retain(|slug| {
+            Self::is_valid_slug(slug) && !SKIP_PLUGIN_SLUGS.contains(&slug.as_str())
+        });
+        candidates
+    }
+
+    /// Convert a localized object name or script handle to a plugin slug guess
+    fn localized_name_to_slug(name: &str) -> String {
+        const SUFFIXES: [&str; 11] = [
+            "_vars",
+            "_params",
+            "_object",
+            "_obj",
+            "_ajax",
+            "_data",
+            "_settings",
+            "_config",
+            "_l10n",
+            "_i18n",
+            "_js",
+        ];
+
+        let mut slug = name.to_ascii_lowercase();
+        if let Some(stripped) = SUFFIXES.iter().find_map(|suffix| slug.strip_suffix(suffix)) {
+            slug = stripped.to_string();
+        }
+        slug.replace('_', "-").trim_matches('-').to_string()
+    }
+
+    /// Detect a CDN host serving `wp-content` assets instead of the site itself
+    ///
+    /// Returns the most frequently referenced foreign host, if any.
+    fn detect_cdn_host(&self, html: &str) -> Option<String> {
+        let re = Regex::new(
+            r"(?:https?:)?\\?/\\?/([a-zA-Z0-9.-]+(?::[0-9]+)?)\\?/wp-content\\?/(?:themes|plugins|mu-plugins|uploads)",
+        )
+        .ok()?;
+        let site_host = self.base_url.host_str()?;
+
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for caps in re.captures_iter(html) {
+            let host = caps.get(1)?.as_str();
+            let host_name = host.split(':').next().unwrap_or(host);
+            if !host_name.eq_ignore_ascii_case(site_host) {
+                *counts.entry(host).or_default() += 1;
+            }
+        }
+
+        counts
+            .into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+            .map(|(host, _)| host.to_lowercase())
+    }
+
+    /// Detect a custom content directory set via `WP_CONTENT_URL`
+    ///
+    /// Hardened sites move `wp-content` (e.g., to `/app/`), so none of the
+    /// `wp-content` patterns match. Looks for asset paths shaped like
+    /// `/{dir}/themes/{slug}/` or `/{dir}/plugins/{slug}/` and returns the most
+    /// referenced directory that also holds a theme.
+    fn detect_content_dir(&self, html: &str) -> Option<String> {
+        let re =
+            Regex::new(r#"([^"'\s<>()=]*?)\\?/(themes|plugins)\\?/[a-zA-Z0-9_-]+\\?/"#).ok()?;
+
+        // Directory -> (references, has a theme)
+        let mut dirs: HashMap<String, (usize, bool)> = HashMap::new();
+        for caps in re.captures_iter(html) {
+            let prefix = caps[1].replace('\\', "");
+            // Drop the scheme and host of absolute URLs
+            let path = match prefix.find("//") {
+                Some(pos) => {
+                    let rest = &prefix[pos + 2..];
+                    rest.find('/').map_or("", |slash| &rest[slash..])
+                }
+                None => prefix.as_str(),
+            };
+            let dir = path.trim_matches('/');
+            if dir.is_empty() || dir.contains("wp-content") || dir.contains("wp-includes") {
+                continue;
+            }
+            let entry = dirs.entry(dir.to_string()).or_default();
+            entry.0 += 1;
+            entry.1 |= &caps[2] == "themes";
+        }
+
+        let (dir, _) = dirs
+            .into_iter()
+            .filter(|(_, (_, has_theme))| *has_theme)
+            .max_by(|a, b| a.1.0.cmp(&b.1.0).then_with(|| b.0.cmp(&a.0)))?;
+        self.record_evidence(
+            format!("content directory /{}/", dir),
+            "page markup",
+            format!("/{}/themes/", dir),
+        );
+        Some(dir)
+    }
+
+    /// Probe plugin directories with HEAD requests, returning the slugs that exist
+    ///
+    /// A `403` (directory listing denied) only counts when a control probe of
+    /// a slug that can't exist gets something else; sites behind a WAF that
+    /// forbids every path would otherwise have every candidate "installed".
+    #[instrument(level = "debug", skip_all, fields(candidates = slugs.len()), ret)]
+    async fn probe_plugin_slugs(&self, slugs: Vec<&str>) -> Vec<String> {
+        if slugs.is_empty() {
+            return Vec::new();
+        }
+        let forbidden_counts = self
+            .plugin_dir_status(&Self::random_slug())
+            .await
+            .is_some_and(|status| status != 403);
+        debug!(forbidden_counts, "plugin directory control probe");
+
+        stream::iter(slugs)
+            .map(|slug| async move {
+                match self.plugin_dir_status(slug).await? {
+                    200 => Some(slug.to_string()),
+                    403 if forbidden_counts => Some(slug.to_string()),
+                    _ => None,
+                }
+            })
+            .buffer_unordered(self.concurrency)
+            .filter_map(|slug| async move { slug })
+            .collect()
+            .await
+    }
+
+    /// HEAD a plugin directory, returning its status code
+    async fn plugin_dir_status(&self, slug: &str) -> Option<u16> {
+        let url = self
+            .base_url
+            .join(&format!("wp-content/plugins/{}/", slug))
+            .ok()?;
+        let response = self.send(self.client.head(url.as_str())).await.ok()?;
+        Some(response.status().as_u16())
+    }
+
+    /// A slug no real plugin uses, for control probes
+    fn random_slug() -> String {
+        format!("wpa-control-{:016x}", RandomState::new().hash_one(0_u8))
+    }
+
+    /// Check that a slug only contains characters WordPress allows in directory names
+    fn is_valid_slug(slug: &str) -> bool {
+        !slug.is_empty()
+            && slug
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+
+    /// Find plugin version from HTML
+    fn find_plugin_version(&self, html: &str, slug: &str) -> Option<String> {
+        // Look for version parameters in plugin URLs (supports both plugins and mu-plugins)
+        let pattern = format!(
+            r#"\\?/wp-content\\?/(?:mu-)?plugins\\?/{}\\?/[^'"\s?]*\?([^'"\s<>]+)"#,
+            regex::escape(slug)
+        );
+        let re = Regex::new(&pattern).ok()?;
+        let version = re
+            .captures_iter(html)
+            .find_map(|caps| Self::extract_version_param(caps.get(1)?.as_str()))?;
+
+        // Filter out Unix timestamps (10-digit numbers) and hash-like versions
+        Some(Self::normalize_version(version))
+    }
+
+    /// Read a plugin version from the header comment of its enqueued asset
+    ///
+    /// Fallback for plugins that strip `?ver=` from their URLs: the first JS or
+    /// CSS file referenced for the plugin often starts with a `* Version: X.Y.Z`
+    /// line. Only the first kilobyte is read, and only assets on the site's own
+    /// host are fetched.
+    #[instrument(level = "debug", skip(self, html), ret)]
+    async fn detect_plugin_version_from_asset(&self, html: &str, slug: &str) -> Option<String> {
+        let asset_url = Self::find_plugin_asset_url(html, slug)
+            .and_then(|href| self.base_url.join(&href).ok())
+            .filter(|url| url.host_str() == self.base_url.host_str())?;
+        let body = self.fetch_leading_bytes(&asset_url).await?;
+
+        let version = Self::extract_header_comment_version(&String::from_utf8_lossy(&body))?;
+        self.record_evidence(
+            format!("plugin {} version {}", slug, version),
+            "asset header comment",
+            asset_url.as_str(),
+        );
+        Some(version)
+    }
+
+    /// Fetch the first kilobyte of a file with a range request
+    async fn fetch_leading_bytes(&self, url: &Url) -> Option<Vec<u8>> {
+        let mut response = self
+            .send(self.client.get(url.as_str()).header("range", PROBE_RANGE))
+            .await
+            .ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+
+        // Servers that ignore the range header send the whole file; stop early
+        let mut body = Vec::new();
+        while body.len() < ASSET_HEADER_BYTES {
+            match response.chunk().await.ok()? {
+                Some(chunk) => body.extend_from_slice(&chunk),
+                None => break,
+            }
+        }
+        body.truncate(ASSET_HEADER_BYTES);
+        Some(body)
+    }
+
+    /// Find the first JS or CSS file referenced under a plugin's directory
+    fn find_plugin_asset_url(html: &str, slug: &str) -> Option<String> {
+        let pattern = format!(
+            r#"[^'"\s<>()=]*\\?/wp-content\\?/(?:mu-)?plugins\\?/{}\\?/[^'"\s<>?]+\.(?:js|css)\b"#,
+            regex::escape(slug)
+        );
+        let re = Regex::new(&pattern).ok()?;
+        re.find(html).map(|m| m.as_str().replace('\\', ""))
+    }
+
+    /// Extract a `Version:` line from the leading comment block of an asset
+    fn extract_header_comment_version(text: &str) -> Option<String> {
+        let comment = text.trim_start_matches('\u{feff}').trim_start();
+        let comment = comment.strip_prefix("/*")?;
+        let comment = comment.split("*/").next()?;
+
+        let re = Regex::new(r"(?mi)^[\s*]*Version:\s*v?([0-9][0-9A-Za-z._-]*)").ok()?;
+        let version = re.captures(comment)?.get(1)?.as_str();
+        Some(Self::normalize_version(version))
+    }
+
+    /// Extract a version from a query string (`ver`, `v`, `rev`, or `cache`)
+    ///
+    /// Prefers `ver=` when several version parameters are present.
+    fn extract_version_param(query: &str) -> Option<&str> {
+        let params: Vec<(&str, &str)> = query
+            .split('&')
+            .map(|p| p.strip_prefix("amp;").unwrap_or(p))
+            .filter_map(|p| p.split_once('='))
+            .collect();
+
+        VERSION_PARAMS.iter().find_map(|name| {
+            let (_, value) = params.iter().find(|(key, _)| key == name)?;
+            let end = value
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '.' && c != '-' && c != '_')
+                .unwrap_or(value.len());
+            Some(&value[..end]).filter(|v| !v.is_empty())
+        })
+    }
+
+    /// Normalize version string - detect timestamps and hashes
+    fn normalize_version(version: &str) -> String {
+        // Unix timestamp detection (10 digits, starts with 1 or 2, reasonable range)
+        if version.len() == 10
+            && version.chars().all(|c| c.is_ascii_digit())
+            && version.starts_with(['1', '2'])
+        {
+            return format!("(timestamp:{})", version);
+        }
+
+        // Git commit hash detection (40 hex chars or 7+ hex abbreviation)
+        if (version.len() == 40 || version.len() >= 7)
+            && version.chars().all(|c| c.is_ascii_hexdigit())
+            && !version.chars().all(|c| c.is_ascii_digit())
+        {
+            let short = if version.len() > 7 {
+                &version[..7]
+            } else {
+                version
+            };
+            return format!("(hash:{})", short);
         }
 
         version.to_string()
     }
-}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_valid_url() {
+        // Note: This may fail if example.com resolves to an internal IP in test environment
+        let scanner = Scanner::new("https://example.com");
+        assert!(scanner.is_ok());
+    }
+
+    #[test]
+    fn parse_invalid_url() {
+        let scanner = Scanner::new("not a url");
+        assert!(scanner.is_err());
+    }
+
+    #[test]
+    fn reject_localhost() {
+        let result = Scanner::new("http://localhost");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("localhost"));
+    }
+
+    #[test]
+    fn reject_localhost_subdomain() {
+        let result = Scanner::new("http://foo.localhost");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn reject_file_scheme() {
+        let result = Scanner::new("file:///etc/passwd");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("scheme"));
+    }
+
+    #[test]
+    fn reject_ftp_scheme() {
+        let result = Scanner::new("ftp://example.com");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("scheme"));
+    }
+
+    #[test]
+    fn onion_host_allowed_through_socks_proxy() {
+        let result = Scanner::builder("http://exampleonionaddress.onion")
+            .proxy("socks5h://127.0.0.1:9050")
+            .build();
+        assert!(result.is_ok());
+
+        // Clearnet hosts keep SSRF protection behind the proxy
+        let result = Scanner::builder("http://127.0.0.1")
+            .proxy("socks5h://127.0.0.1:9050")
+            .build();
+        assert!(result.is_err());
+
+        // An HTTP proxy can't resolve hidden services
+        assert!(
+            !ScannerBuilder::new("http://exampleonionaddress.onion")
+                .proxy("http://127.0.0.1:8080")
+                .onion_via_proxy()
+        );
+    }
+
+    #[test]
+    fn onion_host_rejected_without_remote_dns() {
+        // socks5:// resolves the name locally, leaking it to the DNS resolver
+        for proxy in ["socks5://127.0.0.1:9050", "socks4://127.0.0.1:9050"] {
+            let err = Scanner::builder("http://exampleonionaddress.onion")
+                .proxy(proxy)
+                .build()
+                .unwrap_err();
+            assert!(err.to_string().contains("socks5h"));
+        }
+        assert!(
+            Scanner::builder("http://exampleonionaddress.onion")
+                .allow_private(true)
+                .build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn reject_zero_timeout() {
+        let err = Scanner::builder("https://example.com")
+            .timeout(Duration::ZERO)
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("timeout must be non-zero"));
+    }
+
+    #[test]
+    fn reject_invalid_proxy() {
+        let result = Scanner::builder("https://example.com")
+            .proxy("not a proxy")
+            .build();
+        assert!(result.unwrap_err().to_string().contains("invalid proxy"));
+    }
+
+    /// Self-signed CA for certificate loading tests
+    const TEST_CA_PEM: &str = "\
+-----BEGIN CERTIFICATE-----\n\
+MIIBlDCCATugAwIBAgIUMjQhLE5M3lxvdL3QjR9dx4F3bnMwCgYIKoZIzj0EAwIw\n\
+HzEdMBsGA1UEAwwUVGVzdCBJbnRlcmNlcHRpbmcgQ0EwIBcNMjYxMDE2MDc0NzM3\n\
+WhgPMjEyNjA5MjIwNzQ3MzdaMB8xHTAbBgNVBAMMFFRlc3QgSW50ZXJjZXB0aW5n\n\
+IENBMFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAECwtB7B3bo35Bw8YB5o7bM0Fu\n\
+zetkAYBUAqbshCjgD9okA18sqvO4x0Ar07H1L7HMtGBgjqIjHJglRsqcNtOUIKNT\n\
+MFEwHQYDVR0OBBYEFG2srSdreYaqLFyjxLKAJRhzXzAcMB8GA1UdIwQYMBaAFG2s\n\
+rSdreYaqLFyjxLKAJRhzXzAcMA8GA1UdEwEB/wQFMAMBAf8wCgYIKoZIzj0EAwID\n\
+RwAwRAIgJhdqW/KukXfavUjXPJfb4zG50VZ0wRXWqd3XXTFgIQoCIGRm3boYuaEe\n\
+aXrq24yXuRIw1Gg4G4leUtZ1O5ZUWc3l\n\
+-----END CERTIFICATE-----\n";
+
+    #[test]
+    fn add_root_certificate_from_pem_and_file() {
+        Scanner::builder("https://example.com")
+            .add_root_certificate(TEST_CA_PEM)
+            .build()
+            .unwrap();
+
+        let path =
+            std::env::temp_dir().join(format!("wordpress-audit-ca-{}.pem", std::process::id()));
+        std::fs::write(&path, TEST_CA_PEM).unwrap();
+        let result = Scanner::builder("https://example.com")
+            .add_root_certificate(path.to_str().unwrap())
+            .build();
+        std::fs::remove_file(&path).unwrap();
+        result.unwrap();
+    }
+
+    #[tokio::test]
+    async fn accept_language_only_sent_to_target() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-content/plugins/akismet/readme.txt"))
+            .and(header("accept-language", "de-DE"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("Stable tag: 5.3"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let api = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "version": "5.3" })),
+            )
+            .mount(&api)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .api_base(&api.uri())
+            .accept_language("de-DE")
+            .build()
+            .unwrap();
+        assert_eq!(
+            scanner.check_plugin("akismet").await.unwrap().version,
+            "5.3"
+        );
+
+        let api_requests = api.received_requests().await.unwrap();
+        assert_eq!(api_requests.len(), 1);
+        assert!(!api_requests[0].headers.contains_key("accept-language"));
+    }
+
+    #[tokio::test]
+    async fn http1_only_scanner_reaches_target() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-content/plugins/akismet/readme.txt"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("Stable tag: 5.3"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .offline(true)
+            .http1_only(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            scanner.check_plugin("akismet").await.unwrap().version,
+            "5.3"
+        );
+    }
+
+    #[test]
+    fn reject_unloadable_root_certificate() {
+        let err = Scanner::builder("https://example.com")
+            .add_root_certificate("/nonexistent/ca.pem")
+            .build()
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::HttpClient(ref msg) if msg.contains("cannot read certificate"))
+        );
+
+        let err = Scanner::builder("https://example.com")
+            .add_root_certificate(
+                "-----BEGIN CERTIFICATE-----\nnot base64\n-----END CERTIFICATE-----",
+            )
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, Error::HttpClient(_)));
+    }
+
+    #[test]
+    fn allow_host_literal_ip() {
+        let scanner = Scanner::builder("http://10.0.0.5")
+            .allow_host("10.0.0.5")
+            .build();
+        assert!(scanner.is_ok());
+
+        // Other private hosts are still rejected
+        let scanner = Scanner::builder("http://10.0.0.6")
+            .allow_host("10.0.0.5")
+            .build();
+        assert!(scanner.is_err());
+    }
+
+    #[test]
+    fn allow_host_hostname() {
+        let scanner = Scanner::builder("http://wp.localhost:8080")
+            .allow_host("WP.localhost")
+            .build();
+        assert!(scanner.is_ok());
+
+        let scanner = Scanner::builder("http://other.localhost")
+            .allow_host("wp.localhost")
+            .build();
+        assert!(scanner.is_err());
+    }
+
+    #[test]
+    fn allow_host_ipv6() {
+        let scanner = Scanner::builder("http://[::1]:8080")
+            .allow_host("0:0:0:0:0:0:0:1")
+            .build();
+        assert!(scanner.is_ok());
+    }
+
+    #[test]
+    fn internal_ip_detection() {
+        use std::net::Ipv4Addr;
+
+        // Private ranges
+        assert!(Scanner::is_internal_ip(IpAddr::V4(Ipv4Addr::new(
+            10, 0, 0, 1
+        ))));
+        assert!(Scanner::is_internal_ip(IpAddr::V4(Ipv4Addr::new(
+            172, 16, 0, 1
+        ))));
+        assert!(Scanner::is_internal_ip(IpAddr::V4(Ipv4Addr::new(
+            192, 168, 1, 1
+        ))));
+
+        // Loopback
+        assert!(Scanner::is_internal_ip(IpAddr::V4(Ipv4Addr::new(
+            127, 0, 0, 1
+        ))));
+
+        // Link-local
+        assert!(Scanner::is_internal_ip(IpAddr::V4(Ipv4Addr::new(
+            169, 254, 1, 1
+        ))));
+
+        // Public IP should pass
+        assert!(!Scanner::is_internal_ip(IpAddr::V4(Ipv4Addr::new(
+            8, 8, 8, 8
+        ))));
+        assert!(!Scanner::is_internal_ip(IpAddr::V4(Ipv4Addr::new(
+            93, 184, 216, 34
+        ))));
+    }
+
+    #[test]
+    fn normalize_semantic_version() {
+        assert_eq!(Scanner::normalize_version("1.2.3"), "1.2.3");
+        assert_eq!(Scanner::normalize_version("22.0.0"), "22.0.0");
+        assert_eq!(Scanner::normalize_version("7.0-alpha"), "7.0-alpha");
+    }
+
+    #[test]
+    fn normalize_timestamp_version() {
+        // Unix timestamps should be marked
+        assert_eq!(
+            Scanner::normalize_version("1748271784"),
+            "(timestamp:1748271784)"
+        );
+        assert_eq!(
+            Scanner::normalize_version("1748268723"),
+            "(timestamp:1748268723)"
+        );
+    }
+
+    #[test]
+    fn normalize_hash_version() {
+        // Git hashes should be shortened and marked
+        assert_eq!(
+            Scanner::normalize_version("569ab5664387d06c16a234c9771d3d57fb15720a"),
+            "(hash:569ab56)"
+        );
+        assert_eq!(Scanner::normalize_version("abcdef1"), "(hash:abcdef1)");
+    }
+
+    #[test]
+    fn normalize_date_version() {
+        // Date-like versions (8 digits) should pass through
+        assert_eq!(Scanner::normalize_version("20200121"), "20200121");
+    }
+
+    #[tokio::test]
+    async fn detection_paths_override_defaults() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/news/rss/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("<generator>https://wordpress.org/?v=6.4.2</generator>"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/docs/readme-wp.html"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<br /> Version 6.4.3"))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&format!("{}/blog", server.uri()))
+            .allow_private(true)
+            .detection_paths(DetectionPaths {
+                feed: "/news/rss/".to_string(),
+                readme: "/docs/readme-wp.html".to_string(),
+                ..DetectionPaths::default()
+            })
+            .build()
+            .unwrap();
+        assert_eq!(
+            scanner.detect_version_from_feed().await.as_deref(),
+            Some("6.4.2")
+        );
+        assert_eq!(
+            scanner.detect_version_from_readme().await.as_deref(),
+            Some("6.4.3")
+        );
+    }
+
+    #[test]
+    fn estimates_version_from_rest_routes() {
+        let scanner = Scanner::new("https://example.com").unwrap();
+        let root: WpJsonResponse = serde_json::from_str(
+            r#"{"namespaces":["wp/v2"],"routes":{
+                "/wp/v2/posts":{"methods":["GET"]},
+                "/wp/v2/widgets":{"methods":["GET"]},
+                "/wp/v2/global-styles/(?P<id>[\\/\\w-]+)":{"methods":["GET"]}
+            }}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            scanner.estimate_wp_version(Some(&root)).as_deref(),
+            Some(">=5.9")
+        );
+
+        let old: WpJsonResponse =
+            serde_json::from_str(r#"{"routes":{"/wp/v2/posts":{}}}"#).unwrap();
+        assert_eq!(scanner.estimate_wp_version(Some(&old)), None);
+        assert_eq!(scanner.estimate_wp_version(None), None);
+    }
+
+    #[test]
+    fn subdirectory_base_url() {
+        let scanner = Scanner::new("https://example.com/blog").unwrap();
+        assert_eq!(scanner.base_url.as_str(), "https://example.com/blog/");
+        assert_eq!(
+            scanner.base_url.join(WP_FEED_PATH).unwrap().as_str(),
+            "https://example.com/blog/feed/"
+        );
+    }
+
+    #[tokio::test]
+    async fn detect_version_in_subdirectory_install() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/blog/feed/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("<generator>https://wordpress.org/?v=6.4.2</generator>"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/blog/readme.html"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<br /> Version 6.4.2"))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&format!("{}/blog", server.uri()))
+            .allow_private(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            scanner.detect_version_from_feed().await.as_deref(),
+            Some("6.4.2")
+        );
+        assert_eq!(
+            scanner.detect_version_from_readme().await.as_deref(),
+            Some("6.4.2")
+        );
+    }
+
+    #[tokio::test]
+    async fn detect_exposed_debug_log() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-content/debug.log"))
+            .and(header("range", PROBE_RANGE))
+            .respond_with(
+                ResponseTemplate::new(206)
+                    .set_body_raw("[01-Jan-2024 00:00:00 UTC] PHP Notice: ...", "text/plain"),
+            )
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        assert_eq!(scanner.detect_debug_log().await, Some(true));
+    }
+
+    #[tokio::test]
+    async fn file_editor_blocked_or_behind_login() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-admin/theme-editor.php"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .file_edit_probe(true)
+            .build()
+            .unwrap();
+        assert_eq!(scanner.detect_file_editor_blocked().await, Some(true));
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-admin/theme-editor.php"))
+            .respond_with(
+                ResponseTemplate::new(302).insert_header("location", "/wp-login.php?reauth=1"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/wp-login.php"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<form id=\"loginform\">"))
+            .mount(&server)
+            .await;
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .file_edit_probe(true)
+            .build()
+            .unwrap();
+        assert_eq!(scanner.detect_file_editor_blocked().await, Some(false));
+    }
+
+    #[tokio::test]
+    async fn detect_exposed_backup_files() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/wp-config.php.bak"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "application/octet-stream"),
+            )
+            .mount(&server)
+            .await;
+        // Soft-404 page served for any path
+        Mock::given(method("HEAD"))
+            .and(path("/database.sql"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/html; charset=UTF-8"),
+            )
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            scanner.detect_exposed_backups().await,
+            vec!["wp-config.php.bak".to_string()]
+        );
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .backup_files(Vec::new())
+            .build()
+            .unwrap();
+        assert!(scanner.detect_exposed_backups().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn debug_log_soft_404() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-content/debug.log"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw("<html>Not found</html>", "text/html; charset=UTF-8"),
+            )
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        assert_eq!(scanner.detect_debug_log().await, Some(false));
+    }
+
+    #[tokio::test]
+    async fn detect_exposed_wp_cron() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/wp-cron.php"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        assert_eq!(scanner.detect_wp_cron().await, Some(true));
+
+        // A server answering 200 for any path says nothing
+        server.reset().await;
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        assert_eq!(scanner.detect_wp_cron().await, None);
+
+        // Hosts that disable external cron answer 403 or 404
+        server.reset().await;
+        Mock::given(method("HEAD"))
+            .and(path("/wp-cron.php"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+        assert_eq!(scanner.detect_wp_cron().await, Some(false));
+    }
+
+    #[test]
+    fn detect_version_from_core_assets() {
+        let scanner = Scanner::new("https://example.com").unwrap();
+        let html = Html::parse_document(
+            r#"<html><head>
+            <script src="https://example.com/wp-includes/js/wp-emoji-release.min.js?ver=6.4.2"></script>
+            <link rel="stylesheet" href="/wp-includes/css/dist/block-library/style.min.css?ver=6.4.2">
+            <script src="/wp-includes/js/jquery/jquery.min.js?ver=3.7.1"></script>
+            <script src="/wp-includes/js/hoverintent.min.js?ver=1748271784"></script>
+            </head></html>"#,
+        );
+        assert_eq!(
+            scanner.detect_version_from_core_assets(&html).as_deref(),
+            Some("6.4.2")
+        );
+
+        let html = Html::parse_document("<html><head></head></html>");
+        assert!(scanner.detect_version_from_core_assets(&html).is_none());
+    }
+
+    #[tokio::test]
+    async fn detect_version_from_login_page() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-login.php"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<link rel="stylesheet" href="/wp-includes/css/dashicons.min.css?ver=6.4.2">
+                   <link rel="stylesheet" href="/wp-includes/css/buttons.min.css?ver=6.4.2">"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            scanner.detect_version_from_login().await.as_deref(),
+            Some("6.4.2")
+        );
+
+        // Custom login URLs redirect away from wp-login.php
+        server.reset().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-login.php"))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", "/"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"<meta name="generator" content="WordPress 6.4.2">"#),
+            )
+            .mount(&server)
+            .await;
+        assert_eq!(scanner.detect_version_from_login().await, None);
+    }
+
+    #[tokio::test]
+    async fn guarded_resolver_blocks_rebinding() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // Public on the first lookup (build time), loopback afterwards
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let resolver = GuardedResolver {
+            allowed_hosts: Vec::new(),
+            lookup: Arc::new(move |_| {
+                let ip = match counter.fetch_add(1, Ordering::SeqCst) {
+                    0 => "93.184.216.34",
+                    _ => "127.0.0.1",
+                };
+                Ok(vec![ip.parse().unwrap()])
+            }),
+        };
+        let name = |host: &str| host.parse::<Name>().unwrap();
+
+        let addrs: Vec<SocketAddr> = resolver
+            .resolve(name("rebind.test"))
+            .await
+            .unwrap()
+            .collect();
+        assert_eq!(addrs[0].ip(), "93.184.216.34".parse::<IpAddr>().unwrap());
+
+        let err = resolver.resolve(name("rebind.test")).await.err().unwrap();
+        assert!(err.to_string().contains("internal/private IP address"));
+
+        // Allowlisted hosts may resolve to internal addresses
+        let resolver = GuardedResolver {
+            allowed_hosts: vec!["rebind.test".to_string()],
+            ..resolver
+        };
+        assert!(resolver.resolve(name("rebind.test")).await.is_ok());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn scan_many_reports_each_url() {
+        let urls = vec![
+            "ftp://example.com".to_string(),
+            "http://localhost".to_string(),
+        ];
+        let results = Scanner::scan_many(&urls, &Scanner::builder("")).await;
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, "ftp://example.com");
+        assert!(
+            results[0]
+                .1
+                .as_ref()
+                .unwrap_err()
+                .to_string()
+                .contains("scheme")
+        );
+        assert_eq!(results[1].0, "http://localhost");
+        assert!(results[1].1.is_err());
+    }
+
+    #[tokio::test]
+    async fn scan_stream_yields_analysis_per_url() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"<html><head><meta name="generator" content="WordPress 6.4.2"></head></html>"#,
+                "text/html",
+            ))
+            .mount(&server)
+            .await;
+
+        let urls = stream::iter(vec![server.uri(), "ftp://example.com".to_string()]);
+        let config = Scanner::builder("").allow_private(true).offline(true);
+        let mut results: Vec<(String, Result<Analysis>)> =
+            Scanner::scan_stream(urls, &config, 2).collect().await;
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, "ftp://example.com");
+        assert!(results[0].1.is_err());
+        let analysis = results[1].1.as_ref().unwrap();
+        assert_eq!(analysis.wordpress.version, "6.4.2");
+    }
+
+    #[test]
+    fn reject_invalid_api_base() {
+        let result = Scanner::builder("https://example.com")
+            .api_base("not a url")
+            .build();
+        assert!(result.unwrap_err().to_string().contains("API base"));
+
+        let result = Scanner::builder("https://example.com")
+            .api_base("file:///tmp/api")
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn fetch_latest_versions_from_api_base() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let api = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/core/version-check/1.7/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "offers": [{ "version": "6.4.2" }] })),
+            )
+            .mount(&api)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/plugins/info/1.2/"))
+            .and(query_param("slug", "akismet"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "version": "5.3",
+                "active_installs": 6000000,
+                "last_updated": "2024-01-15 3:45pm GMT",
+                "tested": "6.4.2"
+            })))
+            .expect(1)
+            .mount(&api)
+            .await;
+
+        let scanner = Scanner::builder("https://example.com")
+            .api_base(&format!("{}/", api.uri()))
+            .build()
+            .unwrap();
+        assert_eq!(
+            scanner.fetch_wp_latest_version().await.as_deref(),
+            Some("6.4.2")
+        );
+
+        // Second lookup is served from the cache (mock expects one call)
+        for _ in 0..2 {
+            assert_eq!(
+                scanner
+                    .fetch_plugin_latest_version("akismet")
+                    .await
+                    .as_deref(),
+                Some("5.3")
+            );
+        }
+        let plugin = scanner.lookup_plugin("akismet".to_string(), None).await;
+        assert_eq!(plugin.active_installs, Some(6_000_000));
+        assert_eq!(plugin.listed, Some(true));
+        assert_eq!(plugin.last_updated.as_deref(), Some("2024-01-15"));
+        assert_eq!(plugin.tested_up_to.as_deref(), Some("6.4.2"));
+    }
+
+    #[tokio::test]
+    async fn unlisted_plugin_is_not_a_failed_lookup() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let api = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/plugins/info/1.2/"))
+            .and(query_param("slug", "gravityforms"))
+            .respond_with(
+                ResponseTemplate::new(404)
+                    .set_body_json(serde_json::json!({ "error": "Plugin not found." })),
+            )
+            .mount(&api)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/plugins/info/1.2/"))
+            .and(query_param("slug", "flaky"))
+            .respond_with(ResponseTemplate::new(502))
+            .mount(&api)
+            .await;
+
+        let scanner = Scanner::builder("https://example.com")
+            .api_base(&api.uri())
+            .build()
+            .unwrap();
+        let premium = scanner
+            .lookup_plugin("gravityforms".to_string(), None)
+            .await;
+        assert_eq!(premium.listed, Some(false));
+        assert_eq!(premium.latest_version, None);
+        let failed = scanner.lookup_plugin("flaky".to_string(), None).await;
+        assert_eq!(failed.listed, None);
+    }
+
+    #[tokio::test]
+    async fn expired_cache_entries_are_refetched() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let api = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/plugins/info/1.2/"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "version": "5.3" })),
+            )
+            .expect(2)
+            .mount(&api)
+            .await;
+
+        let scanner = Scanner::builder("https://example.com")
+            .api_base(&api.uri())
+            .cache_ttl(Duration::from_millis(50))
+            .build()
+            .unwrap();
+        for _ in 0..2 {
+            assert_eq!(
+                scanner
+                    .fetch_plugin_latest_version("akismet")
+                    .await
+                    .as_deref(),
+                Some("5.3")
+            );
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(
+            scanner
+                .fetch_plugin_latest_version("akismet")
+                .await
+                .as_deref(),
+            Some("5.3")
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_lookups_are_not_cached() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let api = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/plugins/info/1.2/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "version": "5.3" }))
+                    .set_delay(Duration::from_secs(2)),
+            )
+            .up_to_n_times(1)
+            .mount(&api)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/plugins/info/1.2/"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "version": "5.3" })),
+            )
+            .mount(&api)
+            .await;
+
+        let scanner = Scanner::builder("https://example.com")
+            .api_base(&api.uri())
+            .timeout(Duration::from_millis(200))
+            .build()
+            .unwrap();
+        // The first request times out, the second one reaches the API
+        assert!(
+            scanner
+                .fetch_plugin_latest_version("akismet")
+                .await
+                .is_none()
+        );
+        assert_eq!(
+            scanner
+                .fetch_plugin_latest_version("akismet")
+                .await
+                .as_deref(),
+            Some("5.3")
+        );
+    }
+
+    #[tokio::test]
+    async fn server_error_json_is_not_a_listing() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let api = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/plugins/info/1.2/"))
+            .respond_with(
+                ResponseTemplate::new(500)
+                    .set_body_json(serde_json::json!({ "code": "internal_error" })),
+            )
+            .up_to_n_times(1)
+            .mount(&api)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/plugins/info/1.2/"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "version": "5.3" })),
+            )
+            .mount(&api)
+            .await;
+
+        let scanner = Scanner::builder("https://example.com")
+            .api_base(&api.uri())
+            .build()
+            .unwrap();
+        assert!(matches!(
+            scanner.fetch_plugin_info("akismet").await,
+            ApiLookup::Unavailable
+        ));
+        assert!(matches!(
+            scanner.fetch_plugin_info("akismet").await,
+            ApiLookup::Found(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn api_rate_limit_retries_then_gives_up() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let api = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/plugins/info/1.2/"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+            .up_to_n_times(1)
+            .mount(&api)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/plugins/info/1.2/"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "version": "5.3" })),
+            )
+            .mount(&api)
+            .await;
+
+        let scanner = Scanner::builder("https://example.com")
+            .api_base(&api.uri())
+            .build()
+            .unwrap();
+        assert_eq!(
+            scanner
+                .fetch_plugin_latest_version("akismet")
+                .await
+                .as_deref(),
+            Some("5.3")
+        );
+
+        // A persistent 429 is counted and not cached
+        api.reset().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+            .expect(u64::from(API_RATE_LIMIT_RETRIES) + 1)
+            .mount(&api)
+            .await;
+        assert_eq!(scanner.fetch_plugin_latest_version("wpforms").await, None);
+        assert_eq!(scanner.records.lock().unwrap().api_rate_limited, 1);
+        assert!(
+            !scanner
+                .api_cache
+                .lock()
+                .unwrap()
+                .keys()
+                .any(|url| url.contains("wpforms"))
+        );
+    }
+
+    #[test]
+    fn install_dir_from_final_url() {
+        let url = Url::parse("https://www.example.com/blog/index.php?p=1#top").unwrap();
+        assert_eq!(
+            Scanner::install_dir(&url).as_str(),
+            "https://www.example.com/blog/"
+        );
+        let url = Url::parse("https://www.example.com").unwrap();
+        assert_eq!(
+            Scanner::install_dir(&url).as_str(),
+            "https://www.example.com/"
+        );
+    }
+
+    #[tokio::test]
+    async fn scan_follows_redirect_to_final_url() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(
+                ResponseTemplate::new(301)
+                    .insert_header("location", format!("{}/site/", server.uri()).as_str()),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/site/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html></html>"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/site/feed/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("<generator>https://wordpress.org/?v=6.4.2</generator>"),
+            )
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .api_base(&server.uri())
+            .build()
+            .unwrap();
+        let result = scanner.scan().await.unwrap();
+        assert_eq!(result.url.as_str(), format!("{}/", server.uri()));
+        assert_eq!(result.final_url.as_str(), format!("{}/site/", server.uri()));
+        assert_eq!(result.wordpress_version.as_deref(), Some("6.4.2"));
+    }
+
+    #[tokio::test]
+    async fn detect_components_from_link_preload_headers() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(
+                        "link",
+                        "</wp-content/plugins/perfmatters/css/lazy.css?ver=2.1.4>; rel=preload; as=style, \
+                         </wp-content/themes/astra/style.min.css?ver=4.6.3>; rel=preload; as=style",
+                    )
+                    .set_body_string("<html><body>No assets here</body></html>"),
+            )
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .api_base(&server.uri())
+            .build()
+            .unwrap();
+        let result = scanner.scan().await.unwrap();
+
+        let theme = result.theme.unwrap();
+        assert_eq!(theme.slug, "astra");
+        assert_eq!(theme.version.as_deref(), Some("4.6.3"));
+        assert_eq!(result.plugins.len(), 1);
+        assert_eq!(result.plugins[0].slug, "perfmatters");
+        assert_eq!(result.plugins[0].version.as_deref(), Some("2.1.4"));
+    }
+
+    #[test]
+    fn extract_theme_bundled_slugs_requires_every_reference_in_theme() {
+        let html = r#"
+            <script src="/wp-content/themes/bridge/framework/wp-content/plugins/qode-slider/a.js"></script>
+            <script src="/wp-content/themes/bridge/framework/wp-content/plugins/js_composer/b.js"></script>
+            <link href="/wp-content/plugins/js_composer/c.css?ver=7.0">
+            <link href="/wp-content/plugins/akismet/d.css">
+        "#;
+
+        let bundled = Scanner::extract_theme_bundled_slugs(html, "bridge");
+        assert_eq!(bundled, HashSet::from(["qode-slider".to_string()]));
+
+        // Nothing is bundled in a different theme
+        assert!(Scanner::extract_theme_bundled_slugs(html, "astra").is_empty());
+    }
+
+    #[test]
+    fn extract_rest_namespace_plugins() {
+        let namespaces: Vec<String> = ["oembed/1.0", "wp/v2", "wpforms/v1", "yoast/v1", "wc/v3"]
+            .map(String::from)
+            .to_vec();
+        let mut slugs: Vec<String> = Scanner::extract_rest_namespace_plugins(&namespaces)
+            .into_iter()
+            .collect();
+        slugs.sort();
+        assert_eq!(slugs, vec!["wordpress-seo", "wpforms"]);
+    }
+
+    #[tokio::test]
+    async fn detect_plugins_from_rest_namespaces() {
+        let server = wiremock::MockServer::start().await;
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .offline(true)
+            .build()
+            .unwrap();
+        let document = Html::parse_document("<html></html>");
+        let namespaces = vec!["elementor/v1".to_string()];
+
+        let plugins = scanner
+            .detect_plugins(&document, &[], &namespaces, None)
+            .await;
+        assert_eq!(plugins.len(), 1);
+        assert_eq!(plugins[0].slug, "elementor");
+        assert!(plugins[0].version.is_none());
+    }
+
+    #[tokio::test]
+    async fn route_namespaces_are_kept_apart() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-json/"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "namespaces": ["wp/v2"],
+                "routes": { "/wp/v2/posts": {}, "/legacy-api/items": {} }
+            })))
+            .mount(&server)
+            .await;
+        // A WAF forbids every plugin directory
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .offline(true)
+            .build()
+            .unwrap();
+        let result = scanner.scan().await.unwrap();
+        assert_eq!(result.rest_namespaces, vec!["wp/v2"]);
+        assert_eq!(result.rest_route_namespaces, vec!["legacy-api"]);
+        assert!(result.plugins.is_empty());
+    }
+
+    #[test]
+    fn route_namespaces_from_rest_index() {
+        let root: WpJsonResponse = serde_json::from_str(
+            r#"{"routes":{
+                "/":{},
+                "/wp/v2/posts":{},
+                "/oembed/1.0/embed":{},
+                "/tribe/events/v1/events/(?P<id>\\d+)":{},
+                "/my-plugin/v1":{},
+                "/legacy-api/items":{}
+            }}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            Scanner::route_namespaces(Some(&root)),
+            vec![
+                "legacy-api",
+                "my-plugin/v1",
+                "oembed/1.0",
+                "tribe/events/v1",
+                "wp/v2"
+            ]
+        );
+
+        // Route listing disabled
+        let root: WpJsonResponse = serde_json::from_str(r#"{"namespaces":["wp/v2"]}"#).unwrap();
+        assert!(Scanner::route_namespaces(Some(&root)).is_empty());
+        assert!(Scanner::route_namespaces(None).is_empty());
+    }
+
+    #[test]
+    fn rest_namespace_candidates_skip_known_and_core() {
+        let namespaces: Vec<String> = [
+            "wp/v2",
+            "wc/store/v1",
+            "oembed/1.0",
+            "yoast/v1",
+            "tribe/events/v1",
+            "my-plugin/v1",
+            "legacy-api",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let expected: HashSet<String> = ["my-plugin", "legacy-api"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(Scanner::rest_namespace_candidates(&namespaces), expected);
+    }
+
+    #[tokio::test]
+    async fn detect_plugins_probes_unknown_rest_namespaces() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/wp-content/plugins/my-plugin/"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .offline(true)
+            .build()
+            .unwrap();
+        let document = Html::parse_document("<html></html>");
+        let namespaces = vec!["my-plugin/v1".to_string(), "other-thing/v2".to_string()];
+
+        let plugins = scanner
+            .detect_plugins(&document, &[], &namespaces, None)
+            .await;
+        let slugs: Vec<&str> = plugins.iter().map(|p| p.slug.as_str()).collect();
+        assert_eq!(slugs, vec!["my-plugin"]);
+    }
+
+    #[test]
+    fn extract_ajax_action_plugins_from_inline_scripts() {
+        let document = Html::parse_document(
+            r#"<script>
+                jQuery.post(ajaxurl, { action: 'wpforms_submit', form_id: 12 });
+                fetch('/wp-admin/admin-ajax.php?action=yith_wcwl_add_to_wishlist&id=3');
+                var cfg = {"ajaxurl":"\/wp-admin\/admin-ajax.php","action":"cmplz_store_consent"};
+                $.ajax({ data: { action: 'load_more_posts' } });
+               </script>
+               <script src="/app.js?action=gf_button_get_form"></script>
+               <form><input name="action" value="mc4wp_form"></form>"#,
+        );
+        let slugs = Scanner::extract_ajax_action_plugins(&document);
+        let expected: HashSet<String> = ["wpforms", "yith-woocommerce-wishlist", "complianz-gdpr"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(slugs, expected);
+    }
+
+    #[tokio::test]
+    async fn detect_plugins_from_ajax_actions() {
+        let server = wiremock::MockServer::start().await;
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .offline(true)
+            .build()
+            .unwrap();
+        let document = Html::parse_document(
+            r#"<script>var nf = { action: "nf_ajax_submit", security: "abc" };</script>"#,
+        );
+
+        let plugins = scanner.detect_plugins(&document, &[], &[], None).await;
+        assert_eq!(plugins.len(), 1);
+        assert_eq!(plugins[0].slug, "ninja-forms");
+        let (_, evidence, _) = scanner.collected_records();
+        assert!(
+            evidence
+                .iter()
+                .any(|e| e.finding == "plugin ninja-forms" && e.source == "AJAX action")
+        );
+    }
+
+    #[tokio::test]
+    async fn detect_plugins_drops_theme_bundled_modules() {
+        let server = wiremock::MockServer::start().await;
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .offline(true)
+            .build()
+            .unwrap();
+        let document = Html::parse_document(
+            r#"<script src="/wp-content/themes/bridge/inc/wp-content/plugins/qode-slider/a.js"></script>
+               <script src="/wp-content/themes/bridge/wp-content/plugins/assets/b.js"></script>
+               <link href="/wp-content/plugins/akismet/a.css?ver=5.3">"#,
+        );
+        let theme = ThemeInfo {
+            slug: "bridge".to_string(),
+            version: None,
+            latest_version: None,
+            disputed_version: None,
+            last_updated: None,
+            tested_up_to: None,
+        };
+
+        let plugins = scanner
+            .detect_plugins(&document, &[], &[], Some(&theme))
+            .await;
+        let slugs: Vec<&str> = plugins.iter().map(|p| p.slug.as_str()).collect();
+        assert_eq!(slugs, vec!["akismet"]);
+    }
+
+    #[tokio::test]
+    async fn skip_plugins_extends_default_list() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<link href="/wp-content/plugins/akismet/a.css?ver=5.3">
+                   <link href="/wp-content/plugins/edge-cache/b.css">
+                   <link href="/wp-content/plugins/index/c.css">"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .api_base(&server.uri())
+            .skip_plugins(vec!["edge-cache".to_string()])
+            .build()
+            .unwrap();
+        let result = scanner.scan().await.unwrap();
+
+        let slugs: Vec<&str> = result.plugins.iter().map(|p| p.slug.as_str()).collect();
+        assert_eq!(slugs, vec!["akismet"]);
+    }
+
+    #[tokio::test]
+    async fn partial_results_when_homepage_forbidden() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/feed/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("<generator>https://wordpress.org/?v=6.4.2</generator>"),
+            )
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .api_base(&server.uri())
+            .build()
+            .unwrap();
+        let result = scanner.scan().await.unwrap();
+
+        assert!(result.partial);
+        assert!(result.wordpress_detected);
+        assert_eq!(result.wordpress_version.as_deref(), Some("6.4.2"));
+        assert_eq!(result.failed_probes, vec!["/: HTTP error: status 403"]);
+        assert_eq!(result.maintenance_mode, None);
+    }
+
+    #[tokio::test]
+    async fn homepage_503_is_maintenance() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(503).insert_header("retry-after", "600"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/feed/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("<generator>https://wordpress.org/?v=6.4.2</generator>"),
+            )
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .offline(true)
+            .build()
+            .unwrap();
+        let result = scanner.scan().await.unwrap();
+
+        assert!(result.partial);
+        assert_eq!(result.maintenance_mode, Some(true));
+        assert!(
+            result
+                .evidence
+                .iter()
+                .any(|e| e.finding == "maintenance mode" && e.source == "homepage status")
+        );
+    }
+
+    #[tokio::test]
+    async fn homepage_error_when_every_probe_fails() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .api_base(&server.uri())
+            .build()
+            .unwrap();
+        let err = scanner.scan().await.unwrap_err();
+        assert!(matches!(err, Error::HttpStatus(403)));
+    }
+
+    #[tokio::test]
+    async fn scan_records_evidence() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<meta name="generator" content="WordPress 6.4.2">
+                   <link rel="stylesheet" href="/wp-content/themes/astra/style.css?ver=4.6">
+                   <link href="/wp-content/plugins/akismet/a.css?ver=5.3">"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .offline(true)
+            .build()
+            .unwrap();
+        let result = scanner.scan().await.unwrap();
+        let evidence: Vec<String> = result.evidence.iter().map(ToString::to_string).collect();
+
+        assert_eq!(
+            evidence,
+            vec![
+                "WordPress version 6.4.2 via meta[name=generator] (WordPress 6.4.2)",
+                "theme astra via stylesheet link (/wp-content/themes/astra/style.css?ver=4.6)",
+                "plugin akismet via page markup (wp-content/plugins/akismet/)",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn scan_evaluates_every_signal() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("set-cookie", "wp_lang=en_US; path=/")
+                    .set_body_string(r#"<meta name="generator" content="WordPress 6.4.2">"#),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/wp-json/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"namespaces":["wp/v2"]}"#))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .offline(true)
+            .build()
+            .unwrap();
+        let result = scanner.scan().await.unwrap();
+        let sources: Vec<&str> = result
+            .evidence
+            .iter()
+            .filter(|e| e.finding.starts_with("WordPress"))
+            .map(|e| e.source.as_str())
+            .collect();
+
+        assert!(sources.contains(&"meta[name=generator]"));
+        assert!(sources.contains(&"REST API"));
+        assert!(sources.contains(&"cookie"));
+        assert_eq!(result.confidence(), Confidence::High);
+    }
+
+    #[tokio::test]
+    async fn asset_paths_count_as_one_method() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<script src="/wp-includes/js/jquery/jquery.min.js"></script>
+                   <link href="/wp-content/themes/astra/style.css">
+                   <script src="/wp-content/plugins/akismet/a.js"></script>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .offline(true)
+            .build()
+            .unwrap();
+        let result = scanner.scan().await.unwrap();
+
+        assert!(result.wordpress_detected);
+        assert!(result.theme.is_some());
+        assert!(!result.plugins.is_empty());
+        assert_eq!(
+            result.detection_methods,
+            BTreeSet::from([DetectionMethod::AssetPaths])
+        );
+        assert!(result.confidence() <= Confidence::Medium);
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[tokio::test]
+    async fn scan_behind_cdn_prefers_asset_paths() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("cf-ray", "8a1b2c3d4e5f-AMS")
+                    .set_body_string(r#"<link href="/wp-content/themes/astra/style.css">"#),
+            )
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .offline(true)
+            .version_only(true)
+            .build()
+            .unwrap();
+        let result = scanner.scan().await.unwrap();
+
+        assert!(result.wordpress_detected);
+        assert_eq!(result.cdn.as_deref(), Some("Cloudflare"));
+        assert!(
+            result
+                .evidence
+                .iter()
+                .any(|e| e.finding == "WordPress" && e.source == "wp-content path")
+        );
+        // Neither the cookie nor the header probe refetched the homepage
+        let homepage_fetches = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|request| request.url.path() == "/")
+            .count();
+        assert_eq!(homepage_fetches, 1);
+    }
+
+    #[tokio::test]
+    async fn restricted_rest_api_is_a_weak_signal() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        async fn detected(homepage: &str) -> bool {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(homepage))
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/wp-json/"))
+                .respond_with(ResponseTemplate::new(401))
+                .mount(&server)
+                .await;
+
+            let scanner = Scanner::builder(&server.uri())
+                .allow_private(true)
+                .offline(true)
+                .version_only(true)
+                .build()
+                .unwrap();
+            scanner.scan().await.unwrap().wordpress_detected
+        }
+
+        assert!(detected(r#"<link href="/wp-content/themes/astra/style.css">"#).await);
+        assert!(!detected("<p>Hello</p>").await);
+    }
+
+    #[tokio::test]
+    async fn scan_decodes_gzipped_homepage() {
+        use flate2::{Compression, write::GzEncoder};
+        use std::io::Write;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(br#"<meta name="generator" content="WordPress 6.4.2">"#)
+            .unwrap();
+        let body = encoder.finish().unwrap();
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-encoding", "gzip")
+                    .set_body_raw(body, "text/html"),
+            )
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .offline(true)
+            .build()
+            .unwrap();
+        let result = scanner.scan().await.unwrap();
+        assert_eq!(result.wordpress_version.as_deref(), Some("6.4.2"));
+    }
+
+    #[tokio::test]
+    async fn version_only_skips_theme_and_plugins() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<meta name="generator" content="WordPress 6.4.2">
+                   <link rel="stylesheet" href="/wp-content/themes/astra/style.css?ver=4.6">
+                   <link href="/wp-content/plugins/akismet/a.css?ver=5.3">"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .offline(true)
+            .version_only(true)
+            .build()
+            .unwrap();
+        let result = scanner.scan().await.unwrap();
+
+        assert_eq!(result.wordpress_version.as_deref(), Some("6.4.2"));
+        assert!(result.theme.is_none() && result.plugins.is_empty());
+        assert_eq!(result.login_accessible, None);
+        let paths: Vec<String> = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| request.url.path().to_string())
+            .collect();
+        assert_eq!(paths, vec!["/", "/wp-json/"]);
+    }
+
+    #[tokio::test]
+    async fn scan_from_html_skips_homepage_fetch() {
+        use wiremock::MockServer;
+
+        let server = MockServer::start().await;
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .offline(true)
+            .build()
+            .unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-powered-by", "PHP/8.3.4".parse().unwrap());
+        let result = scanner
+            .scan_from_html(
+                r#"<meta name="generator" content="WordPress 6.4.2">
+                   <link href="/wp-content/plugins/akismet/a.css?ver=5.3">"#,
+                headers,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.wordpress_version.as_deref(), Some("6.4.2"));
+        assert_eq!(result.php_version.as_deref(), Some("8.3.4"));
+        assert_eq!(result.plugins[0].slug, "akismet");
+        let requests = server.received_requests().await.unwrap();
+        assert!(requests.iter().all(|request| request.url.path() != "/"));
+    }
+
+    #[tokio::test]
+    async fn probe_reports_each_signal() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("set-cookie", "wp_lang=en_US; path=/")
+                    .set_body_string(
+                        r#"<link rel="pingback" href="/xmlrpc.php">
+                           <script src="/wp-includes/js/jquery/jquery.min.js"></script>"#,
+                    ),
+            )
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        let probe = scanner.probe().await.unwrap();
+        assert_eq!(
+            probe,
+            ProbeResult {
+                cookies: true,
+                wp_includes: true,
+                pingback: true,
+                ..ProbeResult::default()
+            }
+        );
+        assert!(probe.is_wordpress());
+
+        // Only the homepage and the REST API root are fetched
+        let paths: Vec<_> = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| request.url.path().to_string())
+            .collect();
+        assert_eq!(paths, vec!["/", "/wp-json/"]);
+    }
+
+    #[tokio::test]
+    async fn total_timeout_aborts_slow_scan() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .total_timeout(Duration::from_millis(100))
+            .build()
+            .unwrap();
+        let err = scanner.scan().await.unwrap_err();
+        assert!(matches!(err, Error::ScanTimeout(limit) if limit == Duration::from_millis(100)));
+    }
+
+    #[tokio::test]
+    async fn target_rate_limit_honors_retry_after() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/slow/"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "3600"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bare/"))
+            .respond_with(ResponseTemplate::new(429))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        let base = Url::parse(&server.uri()).unwrap();
+        assert_eq!(scanner.fetch_page(&base).await.unwrap(), "ok");
+
+        // Too long a wait, or no Retry-After at all, fails right away
+        for slug in ["slow/", "bare/"] {
+            let err = scanner.fetch_page(&base.join(slug).unwrap()).await;
+            assert!(matches!(err, Err(Error::HttpStatus(429))));
+        }
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .max_retries(0)
+            .build()
+            .unwrap();
+        Mock::given(method("GET"))
+            .and(path("/again/"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let err = scanner.fetch_page(&base.join("again/").unwrap()).await;
+        assert!(matches!(err, Err(Error::HttpStatus(429))));
+    }
+
+    #[tokio::test]
+    async fn max_requests_truncates_scan() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let html: String = (0..50)
+            .map(|i| {
+                format!(
+                    r#"<link href="/wp-content/plugins/fake-{}/a.css?ver=1.0">"#,
+                    i
+                )
+            })
+            .collect();
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(html))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .api_base(&server.uri())
+            .max_requests(5)
+            .build()
+            .unwrap();
+        let result = scanner.scan().await.unwrap();
+        assert!(result.truncated);
+        assert_eq!(result.plugins.len(), 50);
+        assert_eq!(server.received_requests().await.unwrap().len(), 5);
+
+        // A fresh budget for every scan of the same scanner
+        let result = scanner.scan().await.unwrap();
+        assert!(result.truncated);
+        assert_eq!(server.received_requests().await.unwrap().len(), 10);
+    }
+
+    #[tokio::test]
+    async fn offline_skips_api_lookups() {
+        use wiremock::matchers::{method, path, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<meta name="generator" content="WordPress 6.4.2">
+                   <link rel="stylesheet" href="/wp-content/themes/astra/style.css?ver=4.6">
+                   <link href="/wp-content/plugins/akismet/a.css?ver=5.3">"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let api = MockServer::start().await;
+        Mock::given(path_regex(".*"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&api)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .api_base(&api.uri())
+            .offline(true)
+            .build()
+            .unwrap();
+        let result = scanner.scan().await.unwrap();
+
+        assert_eq!(result.wordpress_version.as_deref(), Some("6.4.2"));
+        assert!(result.wordpress_latest.is_none());
+        assert!(result.theme.unwrap().latest_version.is_none());
+        assert!(result.plugins[0].latest_version.is_none());
+    }
+
+    #[tokio::test]
+    async fn reject_redirect_to_private_ip() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", "http://10.0.0.1/"))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_host("127.0.0.1")
+            .build()
+            .unwrap();
+        let err = scanner.scan().await.unwrap_err();
+        assert!(matches!(err, Error::HttpRequest(_)));
+    }
+
+    #[test]
+    fn redirect_check_skips_dns() {
+        let check = |url: &str| Scanner::validate_host_literal(&Url::parse(url).unwrap(), &[]);
+        assert!(check("http://10.0.0.1/").is_err());
+        assert!(check("http://[::1]/").is_err());
+        assert!(check("http://wp.localhost/").is_err());
+        // Names are left to the guarded resolver
+        assert!(check("http://internal.invalid/").is_ok());
+        assert!(check("https://93.184.216.34/").is_ok());
+    }
+
+    #[tokio::test]
+    async fn reject_too_many_redirects() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", "/next/"))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .max_redirects(0)
+            .build()
+            .unwrap();
+        assert!(scanner.scan().await.is_err());
+    }
+
+    #[test]
+    fn version_param_forms() {
+        assert_eq!(Scanner::extract_version_param("ver=1.2.3"), Some("1.2.3"));
+        assert_eq!(Scanner::extract_version_param("v=2.0"), Some("2.0"));
+        assert_eq!(Scanner::extract_version_param("rev=45"), Some("45"));
+        assert_eq!(Scanner::extract_version_param("cache=3.1.4"), Some("3.1.4"));
+        assert_eq!(Scanner::extract_version_param("server=1&x=2"), None);
+    }
+
+    #[test]
+    fn version_param_prefers_ver() {
+        assert_eq!(
+            Scanner::extract_version_param("v=9.9&rev=8&ver=1.0.0"),
+            Some("1.0.0")
+        );
+        assert_eq!(
+            Scanner::extract_version_param("cache=7&amp;v=2.1"),
+            Some("2.1")
+        );
+    }
+
+    #[test]
+    fn extract_header_comment_version_from_asset() {
+        let js = "/*!\n * Contact Widget\n *\n * Version: 2.4.1\n * License: GPL\n */\n(function(){})();";
+        assert_eq!(
+            Scanner::extract_header_comment_version(js).as_deref(),
+            Some("2.4.1")
+        );
+
+        // Only the leading comment block counts
+        let js = "(function(){})();\n/* Version: 9.9.9 */";
+        assert_eq!(Scanner::extract_header_comment_version(js), None);
+        let css = "/* Theme styles */\nbody{}\n/* Version: 1.0 */";
+        assert_eq!(Scanner::extract_header_comment_version(css), None);
+    }
+
+    #[tokio::test]
+    async fn detect_plugin_version_from_asset_header() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-content/plugins/contact-widget/js/main.min.js"))
+            .and(header("range", PROBE_RANGE))
+            .respond_with(ResponseTemplate::new(206).set_body_raw(
+                "/**\n * Version: 3.2.0\n */\nvar a=1;",
+                "application/javascript",
+            ))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .offline(true)
+            .build()
+            .unwrap();
+        let html = r#"<script src="/wp-content/plugins/contact-widget/js/main.min.js"></script>"#;
+        assert_eq!(scanner.find_plugin_version(html, "contact-widget"), None);
+        assert_eq!(
+            scanner
+                .detect_plugin_version_from_asset(html, "contact-widget")
+                .await
+                .as_deref(),
+            Some("3.2.0")
+        );
+
+        // Assets on other hosts are not fetched
+        let html = r#"<script src="https://cdn.example.net/wp-content/plugins/contact-widget/a.js"></script>"#;
+        assert_eq!(
+            scanner
+                .detect_plugin_version_from_asset(html, "contact-widget")
+                .await,
+            None
+        );
+    }
+
+    #[test]
+    fn extract_plugins_from_json_ld() {
+        let document = Html::parse_document(
+            r#"<script type="application/ld+json">
+               {"@context":"https://schema.org","@graph":[
+                 {"@type":"WebSite","name":"Example"},
+                 {"@type":"WebPage","generator":"Yoast SEO 21.7"}
+               ]}
+               </script>
+               <script type="application/ld+json">
+               {"@type":"SoftwareApplication","name":"Rank Math SEO","softwareVersion":"1.0.208"}
+               </script>
+               <script type="application/ld+json">{"generator": "SEOPress",</script>"#,
+        );
+        // A SoftwareApplication describes the page's subject, not the site
+        let found = Scanner::extract_json_ld_plugins(&document);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found["wordpress-seo"].as_deref(), Some("21.7"));
+
+        let document = Html::parse_document(
+            r#"<script type="application/ld+json">{"generator":"The SEO Framework"}</script>"#,
+        );
+        let found = Scanner::extract_json_ld_plugins(&document);
+        assert_eq!(found.get("autodescription"), Some(&None));
+    }
+
+    #[tokio::test]
+    async fn asset_version_beats_json_ld() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<meta name="generator" content="WordPress 6.4.2">
+                   <script type="application/ld+json">{"generator":"Yoast SEO 21.7"}</script>
+                   <link href="/wp-content/plugins/wordpress-seo/a.css?ver=22.0">"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .offline(true)
+            .build()
+            .unwrap();
+        let result = scanner.scan().await.unwrap();
+        let yoast = result
+            .plugins
+            .iter()
+            .find(|plugin| plugin.slug == "wordpress-seo")
+            .unwrap();
+        assert_eq!(yoast.version.as_deref(), Some("22.0"));
+    }
+
+    #[test]
+    fn find_plugin_version_alternate_params() {
+        let scanner = Scanner::new("https://example.com").unwrap();
+        let html = r#"
+            <link href="/wp-content/plugins/alpha/style.css?v=2.1.0">
+            <script src="/wp-content/plugins/beta/app.js?rev=1748271784"></script>
+            <script src="/wp-content/plugins/gamma/app.js?cache=3.0&amp;ver=3.1"></script>
+            <script src="/wp-content/plugins/delta/app.js"></script>
+        "#;
+        assert_eq!(
+            scanner.find_plugin_version(html, "alpha").as_deref(),
+            Some("2.1.0")
+        );
+        assert_eq!(
+            scanner.find_plugin_version(html, "beta").as_deref(),
+            Some("(timestamp:1748271784)")
+        );
+        assert_eq!(
+            scanner.find_plugin_version(html, "gamma").as_deref(),
+            Some("3.1")
+        );
+        assert_eq!(scanner.find_plugin_version(html, "delta"), None);
+    }
+
+    #[test]
+    fn theme_version_from_v_param() {
+        let scanner = Scanner::new("https://example.com").unwrap();
+        let theme = scanner
+            .extract_theme_from_url("/wp-content/themes/astra/style.css?v=4.5.2")
+            .unwrap();
+        assert_eq!(theme.slug, "astra");
+        assert_eq!(theme.version.as_deref(), Some("4.5.2"));
+    }
+
+    #[test]
+    fn detect_wp_from_includes_paths() {
+        let scanner = Scanner::new("https://example.com").unwrap();
+        let detect = |html: &str| scanner.detect_wp_from_includes(html).is_some();
+
+        assert!(detect(
+            r#"<script src="/blog/wp-includes/js/wp-embed.min.js"></script>"#
+        ));
+        assert!(detect(
+            r#"{"concatemoji":"https:\/\/example.com\/wp-includes\/js\/wp-emoji-release.min.js"}"#
+        ));
+        assert!(detect(
+            r#"<link href="//example.com/_static/??/wp-includes/css/a.css">"#
+        ));
+        assert!(!detect(
+            r#"<a href="https://developer.wordpress.org/wp-includes/functions.php">docs</a>"#
+        ));
+        assert!(!detect("<p>Core files live in /wp-includes/ on disk.</p>"));
+    }
+
+    #[test]
+    fn detect_maintenance_mode_markers() {
+        let scanner = Scanner::new("https://example.com").unwrap();
+        assert_eq!(scanner.detect_maintenance_mode(""), None);
+        assert_eq!(
+            scanner.detect_maintenance_mode(r#"<link href="/wp-content/themes/astra/style.css">"#),
+            Some(false)
+        );
+        assert_eq!(
+            scanner.detect_maintenance_mode(
+                "<h1>Briefly unavailable for scheduled maintenance. Check back in a minute.</h1>"
+            ),
+            Some(true)
+        );
+        assert_eq!(
+            scanner.detect_maintenance_mode(
+                r#"<link href="/wp-content/plugins/wp-maintenance-mode/assets/css/style.min.css">
+                   <h1>Coming soon</h1>"#
+            ),
+            Some(true)
+        );
+        // SeedProd also builds ordinary landing pages
+        assert_eq!(
+            scanner.detect_maintenance_mode(
+                r#"<link href="/wp-content/plugins/coming-soon/public/css/tailwind.min.css">
+                   <h1>Spring sale</h1>"#
+            ),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn detect_page_builder_signatures() {
+        let scanner = Scanner::new("https://example.com").unwrap();
+        let detect = |html: &str| {
+            scanner
+                .detect_page_builder(&Html::parse_document(html), html)
+                .map(|builder| (builder.name, builder.version))
+        };
+
+        assert_eq!(
+            detect(
+                r#"<meta name="generator" content="Elementor 3.18.3; features: e_dom_optimization">"#
+            ),
+            Some(("Elementor".to_string(), Some("3.18.3".to_string())))
+        );
+        assert_eq!(
+            detect(r#"<body class="home page fl-builder"></body>"#),
+            Some(("Beaver Builder".to_string(), None))
+        );
+        assert_eq!(
+            detect(r#"<link rel="stylesheet" href="/app/themes/Divi/style.css?ver=4.24">"#),
+            Some(("Divi".to_string(), None))
+        );
+        assert_eq!(
+            detect(r#"<link href="/wp-content/plugins/elementor-pro/a.css">"#),
+            None
+        );
+    }
+
+    #[test]
+    fn conflicting_generator_tags_use_highest() {
+        let scanner = Scanner::new("https://example.com").unwrap();
+        let document = Html::parse_document(
+            r#"<meta name="generator" content="WordPress 6.2.1">
+               <meta name="generator" content="WordPress">
+               <meta name="generator" content="WordPress 6.4.2">"#,
+        );
+        assert_eq!(
+            scanner.detect_version_from_meta(&document).as_deref(),
+            Some("6.4.2")
+        );
+
+        let evidence = scanner.records.lock().unwrap().evidence.clone();
+        assert_eq!(
+            evidence[0].finding,
+            "conflicting WordPress versions 6.2.1, 6.4.2"
+        );
+        assert_eq!(evidence[1].detail, "WordPress 6.4.2");
+    }
+
+    #[tokio::test]
+    async fn theme_version_disputed_by_stylesheet() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-content/themes/astra/style.css"))
+            .and(header("range", PROBE_RANGE))
+            .respond_with(ResponseTemplate::new(206).set_body_raw(
+                "/*\nTheme Name: Astra\nVersion: 4.6.3\n*/\nbody{}",
+                "text/css",
+            ))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .offline(true)
+            .build()
+            .unwrap();
+
+        // Caching plugin rewrote ?ver= into its own build number
+        let document = Html::parse_document(
+            r#"<link rel="stylesheet" href="/wp-content/themes/astra/style.css?ver=7.2">"#,
+        );
+        let theme = scanner.detect_theme(&document, &[]).await.unwrap();
+        assert_eq!(theme.version.as_deref(), Some("7.2"));
+        assert_eq!(theme.disputed_version.as_deref(), Some("4.6.3"));
+
+        // Matching versions (ignoring trailing zeros) are not disputed
+        let document = Html::parse_document(
+            r#"<link rel="stylesheet" href="/wp-content/themes/astra/style.css?ver=4.6.3.0">"#,
+        );
+        let theme = scanner.detect_theme(&document, &[]).await.unwrap();
+        assert_eq!(theme.disputed_version, None);
+    }
+
+    #[tokio::test]
+    async fn scan_emits_progress_events() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<html><script src="/wp-content/plugins/akismet/a.js?ver=5.3"></script></html>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .api_base(&server.uri())
+            .on_progress(move |event| sink.lock().unwrap().push(event))
+            .build()
+            .unwrap();
+        scanner.scan().await.unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.first(), Some(&ScanProgress::FetchingHomepage));
+        assert_eq!(events.last(), Some(&ScanProgress::Done));
+        assert!(events.contains(&ScanProgress::CheckingPlugin("akismet".to_string())));
+    }
+
+    #[test]
+    fn extract_plugin_slugs_from_cdn_urls() {
+        let html = r#"
+            <link href="https://cdn.example.net/wp-content/plugins/elementor/a.css?ver=3.18.0">
+            <script src="//static.example.net/wp-content/mu-plugins/custom-mu/x.js"></script>
+            <script>var cfg = {"url":"https:\/\/cdn.example.net\/wp-content\/plugins\/wpforms\/x.js?ver=1.8.5"};</script>
+            <img src="https://cdn.example.net/wp-content/plugins/index/x.png">
+        "#;
+        let mut slugs: Vec<String> = Scanner::extract_plugin_slugs(html).into_iter().collect();
+        slugs.sort();
+        assert_eq!(slugs, vec!["custom-mu", "elementor", "wpforms"]);
+
+        let scanner = Scanner::new("https://example.com").unwrap();
+        assert_eq!(
+            scanner.find_plugin_version(html, "wpforms").as_deref(),
+            Some("1.8.5")
+        );
+        assert_eq!(
+            scanner.find_plugin_version(html, "elementor").as_deref(),
+            Some("3.18.0")
+        );
+    }
+
+    #[test]
+    fn detect_php_version_from_powered_by() {
+        let mut headers = HeaderMap::new();
+        assert!(Scanner::detect_php_version(&headers).is_none());
+
+        headers.insert("x-powered-by", "PHP/7.4.33".parse().unwrap());
+        assert_eq!(
+            Scanner::detect_php_version(&headers).as_deref(),
+            Some("7.4.33")
+        );
+
+        headers.insert("x-powered-by", "PHP/8.2".parse().unwrap());
+        headers.append("x-powered-by", "WP Engine".parse().unwrap());
+        assert_eq!(
+            Scanner::detect_php_version(&headers).as_deref(),
+            Some("8.2")
+        );
+
+        headers.insert("x-powered-by", "PHP".parse().unwrap());
+        assert!(Scanner::detect_php_version(&headers).is_none());
+    }
+
+    #[test]
+    fn detect_wp_from_head_link_rels() {
+        let scanner = Scanner::new("https://example.com").unwrap();
+        let detect = |html: &str| {
+            scanner
+                .detect_wp_from_head_links(&Html::parse_document(html))
+                .is_some()
+        };
+
+        assert!(detect(
+            r#"<head><link rel="EditURI" type="application/rsd+xml" title="RSD" href="https://example.com/xmlrpc.php?rsd"></head>"#
+        ));
+        assert!(detect(
+            r#"<head><link rel="wlwmanifest" type="application/wlwmanifest+xml" href="https://example.com/wp-includes/wlwmanifest.xml"></head>"#
+        ));
+        assert!(detect(
+            r#"<head><link rel="pingback" href="https://example.com/xmlrpc.php"></head>"#
+        ));
+        assert!(!detect(
+            r#"<head><link rel="stylesheet" href="/style.css"><link rel="pingback" href="https://example.com/pingback"></head>"#
+        ));
+    }
+
+    #[test]
+    fn detect_xmlrpc_url_from_links_or_header() {
+        let scanner = Scanner::new("https://example.com").unwrap();
+        let locate = |html: &str, headers: &HeaderMap| {
+            scanner.detect_xmlrpc_url(&Html::parse_document(html), headers)
+        };
+        let no_headers = HeaderMap::new();
+
+        // A relocated endpoint from the pingback link
+        assert_eq!(
+            locate(
+                r#"<link rel="EditURI" href="https://example.com/xmlrpc.php?rsd">
+                   <link rel="pingback" href="/secure/rpc.php">"#,
+                &no_headers
+            )
+            .as_deref(),
+            Some("https://example.com/secure/rpc.php")
+        );
+        assert_eq!(
+            locate(
+                r#"<link rel="EditURI" href="https://example.com/xmlrpc.php?rsd">"#,
+                &no_headers
+            )
+            .as_deref(),
+            Some("https://example.com/xmlrpc.php")
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-pingback",
+            "https://example.com/blog/xmlrpc.php".parse().unwrap(),
+        );
+        assert_eq!(
+            locate("<html></html>", &headers).as_deref(),
+            Some("https://example.com/blog/xmlrpc.php")
+        );
+        assert_eq!(
+            locate(
+                r#"<link rel="pingback" href="https://other.example.net/xmlrpc.php">"#,
+                &no_headers
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn detect_locale_from_cookie_or_html_lang() {
+        let scanner = Scanner::new("https://example.com").unwrap();
+        let document = Html::parse_document(r#"<html lang="fr-FR"><body></body></html>"#);
+        assert_eq!(
+            scanner
+                .detect_locale(&document, &HeaderMap::new())
+                .as_deref(),
+            Some("fr_FR")
+        );
+
+        // The cookie wins over the markup
+        let mut headers = HeaderMap::new();
+        headers.append(
+            "set-cookie",
+            "wordpress_test_cookie=WP; path=/".parse().unwrap(),
+        );
+        headers.append("set-cookie", "wp_lang=de_DE; path=/".parse().unwrap());
+        assert_eq!(
+            scanner.detect_locale(&document, &headers).as_deref(),
+            Some("de_DE")
+        );
+
+        let document = Html::parse_document(r#"<html lang="<script>"><body></body></html>"#);
+        assert_eq!(scanner.detect_locale(&document, &HeaderMap::new()), None);
+        let document = Html::parse_document("<html><body></body></html>");
+        assert_eq!(scanner.detect_locale(&document, &HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn detect_server_from_header() {
+        let mut headers = HeaderMap::new();
+        assert!(Scanner::detect_server(&headers).is_none());
+
+        headers.insert("server", "nginx/1.18.0 (Ubuntu)".parse().unwrap());
+        assert_eq!(
+            Scanner::detect_server(&headers).as_deref(),
+            Some("nginx/1.18.0 (Ubuntu)")
+        );
+
+        headers.insert("server", " ".parse().unwrap());
+        assert!(Scanner::detect_server(&headers).is_none());
+    }
+
+    #[test]
+    fn detect_cdn_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert!(Scanner::detect_cdn(&headers).is_none());
+
+        headers.insert("x-cache", "HIT".parse().unwrap());
+        assert!(Scanner::detect_cdn(&headers).is_none());
+
+        headers.insert("x-cache", "Hit from cloudfront".parse().unwrap());
+        assert_eq!(
+            Scanner::detect_cdn(&headers),
+            Some(("x-cache", "Amazon CloudFront"))
+        );
+
+        headers.insert("server", "cloudflare".parse().unwrap());
+        assert_eq!(
+            Scanner::detect_cdn(&headers),
+            Some(("server", "Cloudflare"))
+        );
+
+        headers.insert("cf-ray", "8a1b2c3d4e5f-AMS".parse().unwrap());
+        assert_eq!(
+            Scanner::detect_cdn(&headers),
+            Some(("cf-ray", "Cloudflare"))
+        );
+    }
 
     #[test]
-    fn parse_valid_url() {
-        // Note: This may fail if example.com resolves to an internal IP in test environment
-        let scanner = Scanner::new("https://example.com");
-        assert!(scanner.is_ok());
+    fn extract_plugin_slugs_from_inline_scripts() {
+        let document = Html::parse_document(
+            r#"
+            <script id="myplugin-js-extra">
+            var myPluginCfg = {"pluginUrl":"https:\/\/example.com\/wp-content\/plugins\/my-gallery","nonce":"abc"};
+            </script>
+            <script>var other = {"base":"/wp-content/plugins/index"};</script>
+            <script src="/wp-content/plugins/linked"></script>
+        "#,
+        );
+        let slugs: Vec<String> = Scanner::extract_inline_script_plugin_slugs(&document)
+            .into_iter()
+            .collect();
+        assert_eq!(slugs, vec!["my-gallery"]);
     }
 
     #[test]
-    fn parse_invalid_url() {
-        let scanner = Scanner::new("not a url");
-        assert!(scanner.is_err());
+    fn extract_localized_plugin_candidates() {
+        let document = Html::parse_document(
+            r#"
+            <script id="contact-form-7-js-extra">var wpcf7 = {"api":{"root":"\/wp-json\/"}};</script>
+            <script>var booking_calendar_vars = {"ajaxurl":"\/wp-admin\/admin-ajax.php"};</script>
+            <script>var analytics = {"trackingId":"UA-1"};</script>
+        "#,
+        );
+        let mut candidates: Vec<String> = Scanner::extract_localized_plugin_candidates(&document)
+            .into_iter()
+            .collect();
+        candidates.sort();
+        assert_eq!(
+            candidates,
+            vec!["booking-calendar", "contact-form-7", "wpcf7"]
+        );
+
+        assert_eq!(
+            Scanner::localized_name_to_slug("MyPlugin_Params"),
+            "myplugin"
+        );
+        assert_eq!(
+            Scanner::localized_name_to_slug("_wpUtilSettings"),
+            "wputilsettings"
+        );
     }
 
     #[test]
-    fn reject_localhost() {
-        let result = Scanner::new("http://localhost");
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("localhost"));
+    fn detect_cdn_host() {
+        let scanner = Scanner::new("https://example.com").unwrap();
+        let html = r#"
+            <link href="https://example.com/wp-content/themes/astra/style.css">
+            <link href="https://cdn.example.net/wp-content/plugins/elementor/a.css">
+            <script src="https://cdn.example.net/wp-content/plugins/wpforms/b.js"></script>
+        "#;
+        assert_eq!(
+            scanner.detect_cdn_host(html).as_deref(),
+            Some("cdn.example.net")
+        );
+
+        let html = r#"<link href="/wp-content/themes/astra/style.css">"#;
+        assert!(scanner.detect_cdn_host(html).is_none());
     }
 
     #[test]
-    fn reject_localhost_subdomain() {
-        let result = Scanner::new("http://foo.localhost");
-        assert!(result.is_err());
+    fn detect_custom_content_dir() {
+        let scanner = Scanner::new("https://example.com").unwrap();
+        let html = r#"
+            <link rel="stylesheet" href="https://example.com/app/themes/sage/dist/main.css">
+            <script src="/app/plugins/acf/a.js"></script>
+            <script>var cfg = {"base":"https:\/\/example.com\/app\/plugins\/wpforms\/"};</script>
+            <script src="/static/plugins/lightbox/b.js"></script>
+        "#;
+        assert_eq!(scanner.detect_content_dir(html).as_deref(), Some("app"));
+
+        // Plugin-only paths (e.g., a JS library folder) are not enough
+        let html = r#"<script src="/static/plugins/lightbox/b.js"></script>"#;
+        assert!(scanner.detect_content_dir(html).is_none());
     }
 
-    #[test]
-    fn reject_file_scheme() {
-        let result = Scanner::new("file:///etc/passwd");
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("scheme"));
+    #[tokio::test]
+    async fn truncate_oversized_body() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("a".repeat(4096)))
+            .mount(&server)
+            .await;
+
+        let url = Url::parse(&server.uri()).unwrap();
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .max_body_bytes(100)
+            .build()
+            .unwrap();
+        assert_eq!(scanner.fetch_page(&url).await.unwrap().len(), 100);
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .max_body_bytes(100)
+            .strict_body_size(true)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            scanner.fetch_page(&url).await,
+            Err(Error::BodyTooLarge(100))
+        ));
     }
 
-    #[test]
-    fn reject_ftp_scheme() {
-        let result = Scanner::new("ftp://example.com");
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("scheme"));
+    #[tokio::test]
+    async fn decode_invalid_utf8_lossily() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_bytes(b"<html>caf\xe9 WordPress</html>".to_vec()),
+            )
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        let html = scanner
+            .fetch_page(&Url::parse(&server.uri()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(html, "<html>caf\u{FFFD} WordPress</html>");
     }
 
     #[test]
-    fn internal_ip_detection() {
+    fn detect_bare_generator_tag() {
+        let scanner = Scanner::new("https://example.com").unwrap();
+
+        let html = Html::parse_document(
+            r#"<html><head><meta name="generator" content="WordPress"></head></html>"#,
+        );
+        assert!(scanner.detect_wp_from_meta(&html).is_some());
+        assert!(scanner.detect_version_from_meta(&html).is_none());
+
+        let html = Html::parse_document(
+            r#"<html><head><meta name="generator" content="WordPress 6.4.2"></head></html>"#,
+        );
+        assert!(scanner.detect_wp_from_meta(&html).is_some());
+        assert_eq!(
+            scanner.detect_version_from_meta(&html).as_deref(),
+            Some("6.4.2")
+        );
+
+        let html = Html::parse_document(
+            r#"<html><head><meta name="generator" content="WordPressify 1.0"></head></html>"#,
+        );
+        assert!(scanner.detect_wp_from_meta(&html).is_none());
+    }
+
+    #[tokio::test]
+    async fn bind_local_address() {
         use std::net::Ipv4Addr;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
 
-        // Private ranges
-        assert!(Scanner::is_internal_ip(IpAddr::V4(Ipv4Addr::new(
-            10, 0, 0, 1
-        ))));
-        assert!(Scanner::is_internal_ip(IpAddr::V4(Ipv4Addr::new(
-            172, 16, 0, 1
-        ))));
-        assert!(Scanner::is_internal_ip(IpAddr::V4(Ipv4Addr::new(
-            192, 168, 1, 1
-        ))));
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&server)
+            .await;
 
-        // Loopback
-        assert!(Scanner::is_internal_ip(IpAddr::V4(Ipv4Addr::new(
-            127, 0, 0, 1
-        ))));
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .local_address(IpAddr::V4(Ipv4Addr::LOCALHOST))
+            .build()
+            .unwrap();
+        let body = scanner
+            .fetch_page(&Url::parse(&server.uri()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(body, "ok");
+    }
 
-        // Link-local
-        assert!(Scanner::is_internal_ip(IpAddr::V4(Ipv4Addr::new(
-            169, 254, 1, 1
-        ))));
+    #[tokio::test]
+    async fn detect_multisite_signup() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
 
-        // Public IP should pass
-        assert!(!Scanner::is_internal_ip(IpAddr::V4(Ipv4Addr::new(
-            8, 8, 8, 8
-        ))));
-        assert!(!Scanner::is_internal_ip(IpAddr::V4(Ipv4Addr::new(
-            93, 184, 216, 34
-        ))));
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-signup.php"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<form id=\"setupform\">"))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        assert_eq!(scanner.detect_multisite(None).await, Some(true));
     }
 
-    #[test]
-    fn normalize_semantic_version() {
-        assert_eq!(Scanner::normalize_version("1.2.3"), "1.2.3");
-        assert_eq!(Scanner::normalize_version("22.0.0"), "22.0.0");
-        assert_eq!(Scanner::normalize_version("7.0-alpha"), "7.0-alpha");
+    #[tokio::test]
+    async fn detect_single_site_signup_redirect() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-signup.php"))
+            .respond_with(
+                ResponseTemplate::new(302)
+                    .insert_header("location", "/wp-login.php?action=register"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/wp-login.php"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        assert_eq!(scanner.detect_multisite(None).await, Some(false));
+    }
+
+    #[tokio::test]
+    async fn multisite_ambiguous_on_404() {
+        use wiremock::MockServer;
+
+        let server = MockServer::start().await;
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        assert_eq!(scanner.detect_multisite(None).await, None);
+
+        // The REST root alone can still mark a network
+        let root: WpJsonResponse =
+            serde_json::from_str(r#"{"namespaces":["wp/v2","wu/v2"]}"#).unwrap();
+        assert_eq!(scanner.detect_multisite(Some(&root)).await, Some(true));
+    }
+
+    #[tokio::test]
+    async fn multisite_needs_signup_form() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        // A catch-all page answering 200 for any path
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-signup.php"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<h1>Page not found</h1>"))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        assert_eq!(scanner.detect_multisite(None).await, None);
+    }
+
+    #[tokio::test]
+    async fn multisite_signals_disagree() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-signup.php"))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", "/wp-login.php"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/wp-login.php"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        let root: WpJsonResponse = serde_json::from_str(r#"{"namespaces":["wu/v2"]}"#).unwrap();
+        assert_eq!(scanner.detect_multisite(Some(&root)).await, None);
+    }
+
+    #[tokio::test]
+    async fn detect_accessible_login_page() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-login.php"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<form id=\"loginform\">"))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        assert_eq!(scanner.detect_login_page().await, Some(true));
+    }
+
+    #[tokio::test]
+    async fn detect_protected_login_page() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-login.php"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/wp-admin/"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        assert_eq!(scanner.detect_login_page().await, Some(false));
     }
 
     #[test]
-    fn normalize_timestamp_version() {
-        // Unix timestamps should be marked
+    fn extract_login_link_from_homepage() {
+        let scanner = Scanner::new("https://example.com").unwrap();
+        let link = |html: &str| {
+            scanner
+                .extract_login_link(&Html::parse_document(html))
+                .map(|url| url.to_string())
+        };
+
         assert_eq!(
-            Scanner::normalize_version("1748271784"),
-            "(timestamp:1748271784)"
+            link(r#"<div class="wp-block-loginout"><a href="/secret-login/?redirect_to=%2F">Log in</a></div>"#)
+                .as_deref(),
+            Some("https://example.com/secret-login/?redirect_to=%2F")
         );
         assert_eq!(
-            Scanner::normalize_version("1748268723"),
-            "(timestamp:1748268723)"
+            link(
+                r#"<div class="widget_meta"><ul>
+                   <li><a href="https://example.com/feed/">Entries feed</a></li>
+                   <li><a href="https://example.com/door/">Log in</a></li></ul></div>"#
+            )
+            .as_deref(),
+            Some("https://example.com/door/")
+        );
+        assert_eq!(
+            link(r#"<a href="https://example.com/wp-login.php">Sign in</a>"#).as_deref(),
+            Some("https://example.com/wp-login.php")
+        );
+        // Other hosts and unrelated links don't count
+        assert_eq!(
+            link(r#"<a href="https://sso.example.net/login?redirect_to=x">Sign in</a>"#),
+            None
+        );
+        assert_eq!(
+            link(r#"<a href="/cart/?add=1&redirect_to=%2Fshop%2F">Add to cart</a>"#),
+            None
+        );
+        assert_eq!(
+            link(r#"<a href="/members/?redirect_to=%2F">Log in</a>"#).as_deref(),
+            Some("https://example.com/members/?redirect_to=%2F")
+        );
+        assert_eq!(link(r#"<a href="/about/">About</a>"#), None);
+    }
+
+    #[tokio::test]
+    async fn detect_login_relocation_via_admin_redirect() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let other = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-admin/"))
+            .respond_with(
+                ResponseTemplate::new(302)
+                    .insert_header("location", "/secret-login/?redirect_to=%2Fwp-admin%2F"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/secret-login/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<form id=\"loginform\">"))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        let document = Html::parse_document("<html></html>");
+        assert_eq!(
+            scanner
+                .detect_login_relocation(&document, Some(false))
+                .await,
+            (Some(true), Some(format!("{}/secret-login/", server.uri())))
+        );
+
+        // A redirect to a page without the login form is not a relocation
+        Mock::given(method("GET"))
+            .and(path("/wp-admin/"))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", "/my-account/"))
+            .mount(&other)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/my-account/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<h1>My account</h1>"))
+            .mount(&other)
+            .await;
+        let other_scanner = Scanner::builder(&other.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            other_scanner
+                .detect_login_relocation(&document, Some(false))
+                .await,
+            (None, None)
         );
+
+        // A link to the default login page settles it without a probe
+        let document = Html::parse_document(r#"<a href="/wp-login.php">Log in</a>"#);
+        assert_eq!(
+            scanner.detect_login_relocation(&document, None).await,
+            (Some(false), None)
+        );
+        assert_eq!(
+            scanner.detect_login_relocation(&document, Some(true)).await,
+            (Some(false), None)
+        );
+    }
+
+    #[tokio::test]
+    async fn hidden_login_page_is_hardened() {
+        use wiremock::MockServer;
+
+        // Custom login URL: both default paths return 404
+        let server = MockServer::start().await;
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        assert_eq!(scanner.detect_login_page().await, Some(false));
     }
 
     #[test]
-    fn normalize_hash_version() {
-        // Git hashes should be shortened and marked
+    fn with_client_keeps_ssrf_validation() {
+        let result = Scanner::builder("http://localhost")
+            .with_client(Client::new())
+            .build();
+        assert!(result.is_err());
+
+        let result = Scanner::builder("http://localhost")
+            .with_client(Client::new())
+            .allow_private(true)
+            .build();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn conflicting_options_are_rejected() {
+        let conflicts = [
+            Scanner::builder("example.com")
+                .with_client(Client::new())
+                .proxy("socks5h://127.0.0.1:9050"),
+            Scanner::builder("example.com")
+                .with_client(Client::new())
+                .local_address("192.0.2.1".parse().unwrap()),
+            Scanner::builder("example.com")
+                .with_client(Client::new())
+                .http1_only(true),
+            Scanner::builder("example.com")
+                .with_client(Client::new())
+                .max_redirects(3),
+            Scanner::builder("example.com")
+                .with_client(Client::new())
+                .timeout(Duration::from_secs(5)),
+            // An explicit timeout conflicts even when it equals the default
+            Scanner::builder("example.com")
+                .with_client(Client::new())
+                .timeout(DEFAULT_TIMEOUT),
+            Scanner::builder("example.com")
+                .version_only(true)
+                .file_edit_probe(true),
+            Scanner::builder("example.com")
+                .version_only(true)
+                .plugin_wordlist(vec!["akismet".to_string()]),
+        ];
+        for config in conflicts {
+            assert!(matches!(config.build(), Err(Error::ConflictingOptions(_))));
+        }
+
+        let err = Scanner::builder("example.com")
+            .with_client(Client::new())
+            .accept_language("de")
+            .build()
+            .unwrap_err();
         assert_eq!(
-            Scanner::normalize_version("569ab5664387d06c16a234c9771d3d57fb15720a"),
-            "(hash:569ab56)"
+            err.to_string(),
+            "conflicting scanner options: accept_language has no effect with with_client"
         );
-        assert_eq!(Scanner::normalize_version("abcdef1"), "(hash:abcdef1)");
+
+        // Disabled options never conflict
+        let scanner = Scanner::builder("http://127.0.0.1")
+            .allow_private(true)
+            .version_only(false)
+            .file_edit_probe(true)
+            .build();
+        assert!(scanner.is_ok());
+    }
+
+    #[tokio::test]
+    async fn with_client_is_used_for_requests() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .and(header("user-agent", "custom-agent"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .user_agent("custom-agent")
+            .build()
+            .unwrap();
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .with_client(client)
+            .build()
+            .unwrap();
+        let body = scanner
+            .fetch_page(&Url::parse(&server.uri()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(body, "ok");
+    }
+
+    #[tokio::test]
+    async fn rest_api_status_open() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-json/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "name": "Blog", "namespaces": ["wp/v2"] })),
+            )
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        let (status, root) = scanner.fetch_rest_root(&HeaderMap::new()).await;
+        assert_eq!(status, Some(RestApiStatus::Open));
+        assert!(scanner.detect_wp_from_rest_api(root.as_ref()).is_some());
+    }
+
+    #[tokio::test]
+    async fn rest_root_from_link_header() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "name": "Blog", "namespaces": ["wp/v2"] })),
+            )
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        let mut headers = HeaderMap::new();
+        let link = format!(
+            "</wp-content/themes/astra/style.css>; rel=preload, <{}/api/>; rel=\"https://api.w.org/\"",
+            server.uri()
+        );
+        headers.insert("link", link.parse().unwrap());
+        let (status, root) = scanner.fetch_rest_root(&headers).await;
+        assert_eq!(status, Some(RestApiStatus::Open));
+        assert!(scanner.detect_wp_from_rest_api(root.as_ref()).is_some());
+
+        // Foreign hosts are ignored in favor of the default path
+        headers.insert(
+            "link",
+            "<https://other.example/api/>; rel=\"https://api.w.org/\""
+                .parse()
+                .unwrap(),
+        );
+        let (status, _) = scanner.fetch_rest_root(&headers).await;
+        assert_eq!(status, Some(RestApiStatus::Absent));
+    }
+
+    #[tokio::test]
+    async fn rest_api_status_restricted() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-json/"))
+            .respond_with(
+                ResponseTemplate::new(401)
+                    .set_body_json(serde_json::json!({ "code": "rest_not_logged_in" })),
+            )
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        let (status, root) = scanner.fetch_rest_root(&HeaderMap::new()).await;
+        assert_eq!(status, Some(RestApiStatus::Restricted));
+        assert!(root.is_none());
+    }
+
+    #[tokio::test]
+    async fn rest_api_status_absent() {
+        use wiremock::MockServer;
+
+        let server = MockServer::start().await;
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        let (status, root) = scanner.fetch_rest_root(&HeaderMap::new()).await;
+        assert_eq!(status, Some(RestApiStatus::Absent));
+        assert!(root.is_none());
     }
 
     #[test]
-    fn normalize_date_version() {
-        // Date-like versions (8 digits) should pass through
-        assert_eq!(Scanner::normalize_version("20200121"), "20200121");
+    fn slug_validation() {
+        assert!(Scanner::is_valid_slug("contact-form-7"));
+        assert!(Scanner::is_valid_slug("wp_super_cache"));
+        assert!(!Scanner::is_valid_slug(""));
+        assert!(!Scanner::is_valid_slug("../wp-config.php"));
+        assert!(!Scanner::is_valid_slug("foo/bar"));
+    }
+
+    #[tokio::test]
+    async fn probe_plugin_wordlist() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/wp-content/plugins/akismet/"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/wp-content/plugins/wordfence/"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .concurrency(2)
+            .build()
+            .unwrap();
+        let mut found = scanner
+            .probe_plugin_slugs(vec!["akismet", "wordfence", "missing-plugin"])
+            .await;
+        found.sort();
+        assert_eq!(found, vec!["akismet", "wordfence"]);
+    }
+
+    #[tokio::test]
+    async fn probe_ignores_forbidden_when_control_is_forbidden() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/wp-content/plugins/akismet/"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        let found = scanner
+            .probe_plugin_slugs(vec!["akismet", "wordfence", "missing-plugin"])
+            .await;
+        assert_eq!(found, vec!["akismet"]);
+    }
+
+    #[tokio::test]
+    async fn check_plugin_ignores_blanket_forbidden() {
+        use wiremock::matchers::any;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .offline(true)
+            .build()
+            .unwrap();
+        assert!(!scanner.check_plugin("akismet").await.unwrap().is_detected());
+        assert!(!scanner.check_theme("astra").await.unwrap().is_detected());
+    }
+
+    #[tokio::test]
+    async fn check_single_plugin_and_theme() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/wp-content/plugins/akismet/"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/wp-content/plugins/akismet/readme.txt"))
+            .respond_with(ResponseTemplate::new(206).set_body_string(
+                "=== Akismet Anti-spam ===\nRequires at least: 5.8\nStable tag: 5.3\n",
+            ))
+            .mount(&server)
+            .await;
+
+        let api = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/plugins/info/1.2/"))
+            .and(query_param("slug", "akismet"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "version": "5.3.1" })),
+            )
+            .mount(&api)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .api_base(&api.uri())
+            .build()
+            .unwrap();
+        let plugin = scanner.check_plugin("akismet").await.unwrap();
+        assert_eq!(plugin.component_type, ComponentType::Plugin);
+        assert_eq!(plugin.version, "5.3");
+        assert_eq!(plugin.latest_version, "5.3.1");
+        assert!(plugin.is_outdated());
+
+        let theme = scanner.check_theme("astra").await.unwrap();
+        assert!(!theme.is_detected());
+        assert!(matches!(
+            scanner.check_plugin("../etc").await,
+            Err(Error::InvalidUrl(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn check_plugin_budget_is_per_call() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/wp-content/plugins/akismet/"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        // Each check sends a HEAD and a readme request
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .offline(true)
+            .max_requests(2)
+            .build()
+            .unwrap();
+        for _ in 0..3 {
+            let plugin = scanner.check_plugin("akismet").await.unwrap();
+            assert!(plugin.is_detected());
+        }
+    }
+
+    #[tokio::test]
+    async fn detect_woocommerce_from_body_class() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .and(query_param("rest_route", "/wc/store/v1/products"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .mount(&server)
+            .await;
+
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        let html =
+            Html::parse_document(r#"<html><body class="home woocommerce-no-js"></body></html>"#);
+        let info = scanner.detect_woocommerce(&html, None).await.unwrap();
+        assert!(info.store_api_public);
+    }
+
+    #[tokio::test]
+    async fn detect_woocommerce_from_rest_namespace() {
+        use wiremock::MockServer;
+
+        // No mocks mounted: the Store API probe gets a 404
+        let server = MockServer::start().await;
+        let scanner = Scanner::builder(&server.uri())
+            .allow_private(true)
+            .build()
+            .unwrap();
+        let html = Html::parse_document("<html><body></body></html>");
+        let rest_root = WpJsonResponse {
+            name: Some("Shop".to_string()),
+            url: None,
+            namespaces: Some(vec!["wp/v2".to_string(), "wc/v3".to_string()]),
+            routes: None,
+        };
+
+        let info = scanner
+            .detect_woocommerce(&html, Some(&rest_root))
+            .await
+            .unwrap();
+        assert!(!info.store_api_public);
+        assert!(scanner.detect_woocommerce(&html, None).await.is_none());
+    }
+
+    #[test]
+    fn detect_wp_from_response_headers() {
+        let scanner = Scanner::new("https://example.com").unwrap();
+        let header = |name: &str, value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                reqwest::header::HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                value.parse().unwrap(),
+            );
+            headers
+        };
+
+        let link = header(
+            "link",
+            "<https://example.com/wp-json/>; rel=\"https://api.w.org/\"",
+        );
+        assert!(scanner.detect_wp_from_header_map(&link).is_some());
+        let pingback = header("x-pingback", "https://example.com/xmlrpc.php");
+        assert!(scanner.detect_wp_from_header_map(&pingback).is_some());
+        let express = header("x-powered-by", "Express");
+        assert!(scanner.detect_wp_from_header_map(&express).is_none());
+
+        let cookie = header("set-cookie", "wordpress_test_cookie=WP; path=/");
+        assert!(scanner.detect_wp_from_set_cookie(&cookie).is_some());
+        let session = header("set-cookie", "PHPSESSID=abc; path=/");
+        assert!(scanner.detect_wp_from_set_cookie(&session).is_none());
     }
 }